@@ -381,6 +381,9 @@ pub(crate) struct FieldAttrs {
     pub help: Vec<CustomHelp>,
 
     pub(crate) ignore_rustdoc: bool,
+
+    /// take metavar and completion hint from the field type with `ValueHint`
+    pub(crate) value_hint: Option<Span>,
 }
 
 impl Name {
@@ -579,6 +582,8 @@ impl Parse for FieldAttrs {
             let kw = input.parse::<Ident>()?;
             if kw == "ignore_rustdoc" {
                 res.ignore_rustdoc = true;
+            } else if kw == "value_hint" {
+                res.value_hint = Some(kw.span());
             } else if let Some(name) = Name::parse(input, &kw)? {
                 res.naming.push(name);
             } else if let Some(cons) = Consumer::parse(input, &kw)? {
@@ -308,6 +308,27 @@ fn check_many_files_implicit() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn value_hint_from_field_type() {
+    let input: NamedField = parse_quote! {
+        #[bpaf(value_hint)]
+        mode: Option<Mode>
+    };
+    let output = quote! {
+        ::bpaf::long("mode").argument::<Mode>(<Mode as ::bpaf::ValueHint>::METAVAR).value_hint().optional()
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+
+    let input: NamedField = parse_quote! {
+        #[bpaf(positional("MODE"), value_hint)]
+        mode: Mode
+    };
+    let output = quote! {
+        ::bpaf::positional::<Mode>("MODE").value_hint()
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
 #[test]
 fn many_catch() {
     let input: NamedField = parse_quote! {
@@ -23,6 +23,7 @@ pub(crate) struct StructField {
     pub cons: Consumer,
     pub postpr: Vec<Post>,
     pub help: Option<Help>,
+    pub value_hint: bool,
 }
 
 fn derive_consumer(name_present: bool, ty: &Type) -> Result<Consumer> {
@@ -116,6 +117,7 @@ impl ToTokens for StructField {
             cons,
             postpr,
             help,
+            value_hint,
         } = self;
 
         let names = naming.iter().chain(env.iter());
@@ -128,14 +130,33 @@ impl ToTokens for StructField {
 
         let help = help.iter();
 
+        let consumer = match cons {
+            Consumer::Argument {
+                metavar: None,
+                ty: Some(ty),
+                ..
+            } if *value_hint => {
+                quote!(argument::<#ty>(<#ty as ::bpaf::ValueHint>::METAVAR).value_hint())
+            }
+            Consumer::Positional {
+                metavar: None,
+                ty: Some(ty),
+                ..
+            } if *value_hint => {
+                quote!(::bpaf::positional::<#ty>(<#ty as ::bpaf::ValueHint>::METAVAR).value_hint())
+            }
+            _ if *value_hint => quote!(#cons.value_hint()),
+            _ => quote!(#cons),
+        };
+
         match cons.help_placement() {
             HelpPlacement::AtName => {
-                quote!(#prefix #( #names .)* #(help(#help).)* #cons #(.#postpr)*)
+                quote!(#prefix #( #names .)* #(help(#help).)* #consumer #(.#postpr)*)
             }
             HelpPlacement::AtConsumer => {
-                quote!(#prefix #( #names .)* #cons #(.help(#help))* #(.#postpr)*)
+                quote!(#prefix #( #names .)* #consumer #(.help(#help))* #(.#postpr)*)
             }
-            HelpPlacement::NotAvailable => quote!(#prefix #(#names.)* #cons #(.#postpr)*),
+            HelpPlacement::NotAvailable => quote!(#prefix #(#names.)* #consumer #(.#postpr)*),
         }
         .to_tokens(tokens);
     }
@@ -299,6 +320,16 @@ impl StructField {
             }
         }
 
+        if let Some(span) = field_attrs.value_hint {
+            if !matches!(
+                cons,
+                Consumer::Argument { .. } | Consumer::Positional { .. }
+            ) {
+                let msg = "value_hint can only be used with argument or positional";
+                return Err(Error::new(span, msg));
+            }
+        }
+
         let help = match field_attrs.help.pop() {
             Some(h) => Some(Help::Custom(h.doc)),
             None => help,
@@ -311,6 +342,7 @@ impl StructField {
             cons,
             postpr,
             help,
+            value_hint: field_attrs.value_hint.is_some(),
         })
     }
 }
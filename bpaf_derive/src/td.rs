@@ -19,6 +19,7 @@ pub(crate) struct CommandCfg {
     pub(crate) long: Vec<LitStr>,
     pub(crate) short: Vec<LitChar>,
     pub(crate) help: Option<Help>,
+    pub(crate) group: Option<LitStr>,
 }
 
 #[derive(Debug, Default)]
@@ -31,6 +32,7 @@ pub(crate) struct OptionsCfg {
     pub(crate) version: Option<Box<Expr>>,
     pub(crate) max_width: Option<Box<Expr>>,
     pub(crate) fallback_usage: bool,
+    pub(crate) help_command: bool,
 }
 
 #[derive(Debug, Default)]
@@ -227,6 +229,8 @@ impl Parse for TopInfo {
                     "This annotation only makes sense in combination with `options` or `command`",
                 ));
                 }
+            } else if kw == "help_command" {
+                with_options(&kw, options.as_mut(), |opt| opt.help_command = true)?;
             } else if kw == "short" {
                 let short = parse_arg(input)?;
                 with_command(&kw, command.as_mut(), |cfg| cfg.short.push(short))?;
@@ -255,6 +259,9 @@ impl Parse for TopInfo {
             } else if kw == "help" {
                 let help = parse_arg(input)?;
                 with_command(&kw, command.as_mut(), |cfg| cfg.help = Some(help))?;
+            } else if kw == "group" {
+                let group = parse_arg(input)?;
+                with_command(&kw, command.as_mut(), |cfg| cfg.group = Some(group))?;
             } else if kw == "path" {
                 bpaf_path.replace(parse_arg::<syn::Path>(input)?);
             } else if kw == "max_width" {
@@ -357,6 +364,15 @@ impl Parse for Ed {
                         "In this context this attribute requires \"command\" annotation",
                     ));
                 }
+            } else if kw == "group" {
+                if matches!(mode, VariantMode::Command) {
+                    attrs.push(EAttr::CommandGroup(parse_arg(input)?));
+                } else {
+                    return Err(Error::new_spanned(
+                        kw,
+                        "In this context this attribute requires \"command\" annotation",
+                    ));
+                }
             } else if kw == "skip" {
                 skip = true;
             } else if kw == "adjacent" {
@@ -397,6 +413,7 @@ pub(crate) enum EAttr {
     FallbackUsage,
     CommandShort(LitChar),
     CommandLong(LitStr),
+    CommandGroup(LitStr),
     Adjacent,
     Hide,
     UnitShort(Option<LitChar>),
@@ -416,6 +433,7 @@ impl ToTokens for EAttr {
             Self::NamedCommand(n) => quote!(command(#n)),
             Self::CommandShort(n) => quote!(short(#n)),
             Self::CommandLong(n) => quote!(long(#n)),
+            Self::CommandGroup(n) => quote!(group(#n)),
             Self::Adjacent => quote!(adjacent()),
             Self::Descr(d) => quote!(descr(#d)),
             Self::Header(d) => quote!(header(#d)),
@@ -199,6 +199,7 @@ impl ToTokens for Top {
                     header,
                     max_width,
                     fallback_usage,
+                    help_command,
                 } = options;
 
                 let version = version.as_ref().map(|v| quote!(.version(#v)));
@@ -212,16 +213,23 @@ impl ToTokens for Top {
                 } else {
                     None
                 };
+                let help_command = if *help_command {
+                    Some(quote!(.help_command()))
+                } else {
+                    None
+                };
                 let CommandCfg {
                     name,
                     long,
                     short,
                     help,
+                    group,
                 } = command;
                 let name = name.as_ref().expect("Internal bpaf_derive error: Command name was not set! This is a bug, please report it.");
                 let long = long.iter().map(|v| quote!(.long(#v)));
                 let short = short.iter().map(|v| quote!(.short(#v)));
                 let help = help.as_ref().map(|v| quote!(.help(#v)));
+                let group = group.as_ref().map(|v| quote!(.group(#v)));
                 quote! {
                     #vis fn #generate() -> impl ::bpaf::Parser<#ty> {
 
@@ -231,6 +239,7 @@ impl ToTokens for Top {
                         #(.#attrs)*
                         .to_options()
                         #fallback_usage
+                        #help_command
                         #version
                         #descr
                         #header
@@ -241,6 +250,7 @@ impl ToTokens for Top {
                         #(#short)*
                         #(#long)*
                         #help
+                        #group
                         #adjacent
                         #boxed
                     }
@@ -256,6 +266,7 @@ impl ToTokens for Top {
                     header,
                     max_width,
                     fallback_usage,
+                    help_command,
                 } = options;
                 let body = match cargo_helper {
                     Some(cargo) => quote!(::bpaf::cargo_helper(#cargo, #body)),
@@ -267,6 +278,11 @@ impl ToTokens for Top {
                 } else {
                     None
                 };
+                let help_command = if *help_command {
+                    Some(quote!(.help_command()))
+                } else {
+                    None
+                };
                 let version = version.as_ref().map(|v| quote!(.version(#v)));
                 let usage = usage.as_ref().map(|v| quote!(.usage(#v)));
                 let descr = descr.as_ref().map(|v| quote!(.descr(#v)));
@@ -282,6 +298,7 @@ impl ToTokens for Top {
                         #(.#attrs)*
                         .to_options()
                         #fallback_usage
+                        #help_command
                         #version
                         #descr
                         #header
@@ -473,7 +490,7 @@ impl ParsedEnumBranch {
                     attrs.push(EAttr::NamedCommand(ident_to_long(&branch.ident)));
                 }
 
-                EAttr::CommandShort(_) | EAttr::CommandLong(_) => {
+                EAttr::CommandShort(_) | EAttr::CommandLong(_) | EAttr::CommandGroup(_) => {
                     // TODO should probably be a bit more careful here,
                     // new_derive macro addresses that though
                     attrs.push(attr);
@@ -50,6 +50,30 @@ fn fallback_usage_top() {
     assert_eq!(top.to_token_stream().to_string(), expected.to_string());
 }
 
+#[test]
+fn help_command_top() {
+    let top: Top = parse_quote! {
+        #[bpaf(options, help_command)]
+        struct Opts {
+            verbose: bool
+        }
+    };
+
+    let expected = quote! {
+        fn opts() -> ::bpaf::OptionParser<Opts> {
+            #[allow(unused_imports)]
+            use ::bpaf::Parser;
+            {
+                let verbose = ::bpaf::long("verbose").switch();
+                ::bpaf::construct!(Opts { verbose, })
+            }
+            .to_options()
+            .help_command()
+        }
+    };
+    assert_eq!(top.to_token_stream().to_string(), expected.to_string());
+}
+
 #[test]
 fn fallback_usage_subcommand() {
     let input: Top = parse_quote! {
@@ -1135,6 +1159,55 @@ fn single_unit_adjacent_command() {
     assert_eq!(top.to_token_stream().to_string(), expected.to_string());
 }
 
+#[test]
+fn command_groups() {
+    let top: Top = parse_quote! {
+        #[bpaf(command, group("Plumbing commands"))]
+        struct CatFile;
+    };
+
+    let expected = quote! {
+        fn cat_file() -> impl ::bpaf::Parser<CatFile> {
+            #[allow(unused_imports)]
+            use ::bpaf::Parser;
+            ::bpaf::pure(CatFile)
+                .to_options()
+                .command("cat-file")
+                .group("Plumbing commands")
+        }
+    };
+    assert_eq!(top.to_token_stream().to_string(), expected.to_string());
+
+    let top: Top = parse_quote! {
+        enum Cmd {
+            #[bpaf(command, group("Porcelain commands"))]
+            Commit,
+            #[bpaf(command("cat-file"), short('c'), group("Plumbing commands"))]
+            CatFile,
+        }
+    };
+
+    let expected = quote! {
+        fn cmd() -> impl ::bpaf::Parser<Cmd> {
+            #[allow(unused_imports)]
+            use ::bpaf::Parser;
+            {
+                let alt0 = ::bpaf::pure(Cmd::Commit)
+                    .to_options()
+                    .command("commit")
+                    .group("Porcelain commands");
+                let alt1 = ::bpaf::pure(Cmd::CatFile)
+                    .to_options()
+                    .command("cat-file")
+                    .short('c')
+                    .group("Plumbing commands");
+                ::bpaf::construct!([alt0, alt1, ])
+            }
+        }
+    };
+    assert_eq!(top.to_token_stream().to_string(), expected.to_string());
+}
+
 #[test]
 fn ingore_doc_comment_top_level_1() {
     let top: Top = parse_quote! {
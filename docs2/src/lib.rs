@@ -92,7 +92,7 @@ $ app {all_args}<br>
 </div>
 "
         )?,
-        Err(ParseFailure::Stdout(buf, full)) => writeln!(
+        Err(ParseFailure::Stdout(buf, full, _)) => writeln!(
             res,
             "
 <div class='bpaf-doc'>
@@ -102,7 +102,7 @@ $ app {all_args}<br>
 ",
             buf.render_html(full, true)
         )?,
-        Err(ParseFailure::Stderr(buf)) => writeln!(
+        Err(ParseFailure::Stderr(buf, _)) => writeln!(
             res,
             "
 <div class='bpaf-doc'>
@@ -112,7 +112,7 @@ $ app {all_args}<br>
 ",
             buf.render_html(true, true)
         )?,
-        Err(ParseFailure::Completion(_)) => todo!(),
+        Err(ParseFailure::Completion(..)) => todo!(),
     };
 
     Ok(())
@@ -2241,6 +2241,17 @@
             //! 		$ your_program --bpaf-complete-style-elvish >> ~/.config/elvish/rc.elv
             //! 		```
             //! 
+            //!      1. **powershell**
+            //!         ```console
+            //!         $ your_program --bpaf-complete-style-powershell >> $PROFILE
+            //!         ```
+            //! 
+            //!      1. **nushell**: script sets an external completer for the whole shell and passes
+            //!         other commands to the completer that was configured before
+            //!         ```console
+            //!         $ your_program --bpaf-complete-style-nushell >> $nu.config-path
+            //!         ```
+            //! 
             //! 4. Restart your shell - you need to done it only once or optionally after bpaf major version
             //!     upgrade: generated completion files contain only instructions how to ask your program for
             //!     possible completions and don’t change even if options are different.
@@ -2248,6 +2259,53 @@
             //! 
             //! 5. Generated scripts rely on your program being accessible in $PATH
             //! 
+            //! For bash, zsh, fish and elvish `your_program --bpaf-complete-install=bash` (or `zsh`, `fish`,
+            //! `elvish`) places the script into a per user location instead and tells where it went:
+            //! `$XDG_DATA_HOME/bash-completion/completions/`, `$XDG_DATA_HOME/zsh/site-functions/` (add it
+            //! to `fpath`), `$XDG_CONFIG_HOME/fish/completions/` or `$XDG_CONFIG_HOME/elvish/rc.elv`.
+            //! `--bpaf-complete-uninstall=bash` removes it, files that were not generated by bpaf are left
+            //! alone.
+            //! 
+            //! If `--bpaf-complete-rev` flags get in the way of your parser, for example with
+            //! `cargo_helper` or [`any`], enable [`OptionParser::completion_env`]: scripts produced with
+            //! `COMPLETE=bash your_program` pass the words to your program as is and set `COMPLETE` instead.
+            //! 
+            //! If your program is slow to start you can generate static scripts instead with
+            //! [`OptionParser::render_bash_completion`], [`OptionParser::render_zsh_completion`] or
+            //! [`OptionParser::render_fish_completion`]. Static scripts contain all the flags, arguments and
+            //! commands and call your program only for values produced by [`Parser::complete`], but they need
+            //! to be regenerated when the parser changes.
+            //! 
+            //! Completion engines such as Fig, Inshellisense or Carapace take a spec instead of a script:
+            //! [`OptionParser::render_fig_spec`] renders a TypeScript spec for Fig and Inshellisense and
+            //! [`OptionParser::render_carapace_spec`] renders a YAML spec for Carapace. Values produced by
+            //! [`Parser::complete`] are completed by calling your program, use
+            //! [`OptionParser::render_fig_spec_with`] or [`OptionParser::render_carapace_spec_with`] to
+            //! change how.
+            //! 
+            //! Editors, REPLs and other programs that embed your parser can ask for completions directly with
+            //! [`OptionParser::completions`], it returns the same candidates shell completion would offer.
+            //! 
+            //! When values for one argument depend on another, for example branch names depend on
+            //! the repository, use [`Parser::complete_with_context`]: the function also gets a
+            //! [`CompletionContext`] with arguments typed so far, `ctx.value("--repo")` gives the repository.
+            //! 
+            //! [`argument`](NamedArg::argument) and [`positional`] with [`PathBuf`](std::path::PathBuf)
+            //! values complete file names and `bool` values complete to `true` or `false` without extra
+            //! annotations. For your own types implement [`ValueHint`] and add `value_hint` to the parser,
+            //! `#[bpaf(value_hint)]` with derive also takes the metavar from the type. Static scripts and
+            //! completion specs use the same hints.
+            //! 
+            //! Completion functions that query other services can be slow: [`timeout`](crate::parsers::ParseComp::timeout)
+            //! limits how long the shell waits for [`Parser::complete`] and [`cache`](crate::parsers::ParseComp::cache)
+            //! keeps produced values on disk for a while, see [`CompletionCache`]. When the function doesn't
+            //! finish in time completion uses stale cached values or offers nothing.
+            //! 
+            //! Value an argument takes from [`display_fallback`](crate::parsers::ParseFallback::display_fallback)
+            //! is offered first, described as `current: default`. Value from the [`env`](crate::NamedArg::env)
+            //! variable can be a secret, add [`complete_env_value`](crate::NamedArg::complete_env_value) to
+            //! the argument to offer it as `current: from $APP_REGION`.
+            //! 
             //! 
             //! 
             //!
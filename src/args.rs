@@ -33,10 +33,16 @@ use crate::{
 /// assert!(value);
 /// ```
 pub struct Args<'a> {
-    items: Box<dyn ExactSizeIterator<Item = OsString> + 'a>,
-    name: Option<String>,
+    pub(crate) items: Box<dyn ExactSizeIterator<Item = OsString> + 'a>,
+    pub(crate) name: Option<String>,
     #[cfg(feature = "autocomplete")]
-    c_rev: Option<usize>,
+    pub(crate) c_rev: Option<usize>,
+    /// Words come from the environment variable completion protocol
+    #[cfg(feature = "autocomplete")]
+    pub(crate) c_verbatim: bool,
+    /// Environment variable used by the completion protocol, if any
+    #[cfg(feature = "autocomplete")]
+    pub(crate) c_env: Option<&'static str>,
 }
 
 impl Args<'_> {
@@ -90,6 +96,10 @@ impl<const N: usize> From<&'static [&'static str; N]> for Args<'_> {
             items: Box::new(value.iter().map(OsString::from)),
             #[cfg(feature = "autocomplete")]
             c_rev: None,
+            #[cfg(feature = "autocomplete")]
+            c_verbatim: false,
+            #[cfg(feature = "autocomplete")]
+            c_env: None,
             name: None,
         }
     }
@@ -101,6 +111,10 @@ impl<'a> From<&'a [&'a std::ffi::OsStr]> for Args<'a> {
             items: Box::new(value.iter().map(OsString::from)),
             #[cfg(feature = "autocomplete")]
             c_rev: None,
+            #[cfg(feature = "autocomplete")]
+            c_verbatim: false,
+            #[cfg(feature = "autocomplete")]
+            c_env: None,
             name: None,
         }
     }
@@ -112,6 +126,10 @@ impl<'a> From<&'a [&'a str]> for Args<'a> {
             items: Box::new(value.iter().map(OsString::from)),
             #[cfg(feature = "autocomplete")]
             c_rev: None,
+            #[cfg(feature = "autocomplete")]
+            c_verbatim: false,
+            #[cfg(feature = "autocomplete")]
+            c_env: None,
             name: None,
         }
     }
@@ -123,6 +141,10 @@ impl<'a> From<&'a [String]> for Args<'a> {
             items: Box::new(value.iter().map(OsString::from)),
             #[cfg(feature = "autocomplete")]
             c_rev: None,
+            #[cfg(feature = "autocomplete")]
+            c_verbatim: false,
+            #[cfg(feature = "autocomplete")]
+            c_env: None,
             name: None,
         }
     }
@@ -134,6 +156,10 @@ impl<'a> From<&'a [OsString]> for Args<'a> {
             items: Box::new(value.iter().map(OsString::from)),
             #[cfg(feature = "autocomplete")]
             c_rev: None,
+            #[cfg(feature = "autocomplete")]
+            c_verbatim: false,
+            #[cfg(feature = "autocomplete")]
+            c_env: None,
             name: None,
         }
     }
@@ -154,6 +180,10 @@ impl Args<'_> {
             items: Box::new(value),
             #[cfg(feature = "autocomplete")]
             c_rev: None,
+            #[cfg(feature = "autocomplete")]
+            c_verbatim: false,
+            #[cfg(feature = "autocomplete")]
+            c_env: None,
             name,
         }
     }
@@ -329,6 +359,12 @@ mod inner {
             let mut comp_scanner = crate::complete_run::ArgScanner {
                 revision: args.c_rev,
                 name: args.name.as_deref(),
+                verbatim: args.c_verbatim,
+                protocol: args
+                    .c_env
+                    .map_or(crate::complete_run::Protocol::Flag, |var| {
+                        crate::complete_run::Protocol::Env(var)
+                    }),
             };
 
             for os in args.items {
@@ -718,6 +754,7 @@ impl State {
                     item: Item::Positional {
                         help: None,
                         metavar,
+                        hint: None,
                     },
                     position: scope.start,
                     scope,
@@ -8,7 +8,7 @@
 //! Examples contain combinatoric usage, for derive usage you should create a parser function and
 //! use `external` annotation.
 
-use crate::{construct, literal, parsers::NamedArg, short, Parser};
+use crate::{construct, doc::ColorChoice, literal, parsers::NamedArg, short, Parser};
 
 /// `--verbose` and `--quiet` flags with results encoded as number
 ///
@@ -184,3 +184,44 @@ where
 {
     parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout()
 }
+
+/// `--color=WHEN` option to pick when to use colors in help and error messages
+///
+/// Accepts `auto`, `always` or `never`, once parsed the choice is applied globally with
+/// [`ColorChoice::set_global`] so it affects any messages `bpaf` prints afterwards, including
+/// help and errors for the same invocation. Without the option global choice is left unchanged
+/// and parser returns it, by default that's [`ColorChoice::Auto`] and environment variables
+/// decide, see [`ColorChoice`] for details.
+///
+/// # Usage
+/// ```rust
+/// # use bpaf::*;
+/// use bpaf::{batteries::*, doc::ColorChoice};
+/// #[derive(Debug, Clone)]
+/// struct Options {
+///     color: ColorChoice,
+///     verbose: bool,
+/// }
+///
+/// let color = color_choice();
+/// let verbose = short('v').switch();
+/// let parser = construct!(Options { color, verbose }).to_options();
+///
+/// let r = parser.run_inner(&["--color", "never"]).unwrap();
+/// assert_eq!(r.color, ColorChoice::Never);
+/// # ColorChoice::Auto.set_global();
+/// ```
+#[must_use]
+pub fn color_choice() -> impl Parser<ColorChoice> {
+    crate::long("color")
+        .help("When to use colors: auto, always or never")
+        .argument::<ColorChoice>("WHEN")
+        .optional()
+        .map(|choice| match choice {
+            Some(choice) => {
+                choice.set_global();
+                choice
+            }
+            None => ColorChoice::global(),
+        })
+}
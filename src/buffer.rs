@@ -8,14 +8,18 @@ use crate::{
     Meta,
 };
 
+#[cfg(feature = "docgen")]
+mod asciidoc;
 mod console;
 mod html;
 #[cfg(feature = "docgen")]
 mod manpage;
+#[cfg(feature = "docgen")]
+mod rst;
 mod splitter;
 
-pub(crate) use self::console::Color;
 use self::console::MAX_WIDTH;
+pub use self::console::{ColorChoice, Theme};
 
 #[cfg(feature = "docgen")]
 pub use manpage::Section;
@@ -147,13 +151,18 @@ impl Doc {
 
     pub(crate) fn write_item(&mut self, item: &Item) {
         match item {
-            Item::Positional { metavar, help: _ } => {
+            Item::Positional {
+                metavar,
+                help: _,
+                hint: _,
+            } => {
                 self.metavar(*metavar);
             }
             Item::Command {
                 name: _,
                 short: _,
                 help: _,
+                group: _,
                 meta: _,
                 info: _,
             } => {
@@ -171,6 +180,7 @@ impl Doc {
                 metavar,
                 env: _,
                 help: _,
+                hint: _,
             } => {
                 self.write_shortlong(name);
                 self.write_char('=', Style::Text);
@@ -224,6 +234,8 @@ impl Doc {
                 Meta::Adjacent(m) | Meta::Subsection(m, _) | Meta::Suffix(m, _) => {
                     go(m, f);
                 }
+                #[cfg(feature = "autocomplete")]
+                Meta::Comp(m, _) => go(m, f),
                 Meta::Skip => {} // => f.write_str("no parameters expected", Style::Text),
                 Meta::CustomUsage(_, u) => {
                     f.doc(u);
@@ -333,7 +345,7 @@ pub struct Doc {
 impl std::fmt::Display for Doc {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let width = f.width().unwrap_or(MAX_WIDTH);
-        f.write_str(&self.render_console(true, Color::Monochrome, width))
+        f.write_str(&self.render_console(true, &Theme::monochrome(), width))
     }
 }
 
@@ -391,6 +403,20 @@ impl Doc {
         Some(res)
     }
 
+    /// Combine brief and detailed help, detailed part is only rendered in the full version
+    pub(crate) fn with_details(brief: &Option<Doc>, details: &Option<Doc>) -> Option<Doc> {
+        match (brief, details) {
+            (Some(brief), Some(details)) => {
+                let mut res = brief.clone();
+                res.write_str("\n\n", Style::Text);
+                res.doc(details);
+                Some(res)
+            }
+            (Some(brief), None) => Some(brief.clone()),
+            (None, details) => details.clone(),
+        }
+    }
+
     #[cfg(feature = "autocomplete")]
     pub(crate) fn to_completion(&self) -> Option<String> {
         let mut s = self.first_line()?.monochrome(false);
@@ -486,6 +512,7 @@ fn extract_sections<'a>(
             name,
             short: _,
             help: _,
+            group: _,
             meta,
             info,
         } = item
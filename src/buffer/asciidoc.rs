@@ -0,0 +1,227 @@
+use crate::{
+    buffer::{
+        html::collect_html,
+        splitter::{split, Chunk},
+        Block, Skip, Style, Token,
+    },
+    Doc, OptionParser, Parser,
+};
+
+impl<T> OptionParser<T> {
+    /// Render command line documentation for the app into AsciiDoc
+    ///
+    /// Documentation covers the whole command tree, one section per command
+    pub fn render_asciidoc(&self, app: impl Into<String>) -> String {
+        collect_html(app.into(), &self.inner.meta(), &self.info, false).render_asciidoc(true)
+    }
+}
+
+/// Push text escaping characters that would start inline markup or an attribute reference
+fn push_text(res: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(c, '*' | '_' | '`' | '#' | '{') {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+}
+
+/// Wrap non blank part of the text into inline markup for a given style
+fn push_styled(res: &mut String, text: &str, style: Style) {
+    let (open, close) = match style {
+        Style::Text => ("", ""),
+        Style::Emphasis | Style::Invalid => ("**", "**"),
+        Style::Literal => ("**`+", "+`**"),
+        Style::Metavar => ("__`+", "+`__"),
+    };
+    // leading whitespace would turn a line into a literal block
+    let text = if res.is_empty() || res.ends_with('\n') {
+        text.trim_start()
+    } else {
+        text
+    };
+    let body = text.trim();
+    if open.is_empty() || body.is_empty() {
+        push_text(res, text);
+        return;
+    }
+    let start = text.len() - text.trim_start().len();
+    res.push_str(&text[..start]);
+    // passthrough between `+` keeps literals and metavars as is, but it can't contain `+`
+    // itself, `pass:c[]` macro can as long as `]` is escaped
+    if !open.ends_with('+') {
+        res.push_str(open);
+        push_text(res, body);
+        res.push_str(close);
+    } else if body.contains('+') {
+        res.push_str(&open[..open.len() - 1]);
+        res.push_str("pass:c[");
+        res.push_str(&body.replace(']', "\\]"));
+        res.push(']');
+        res.push_str(&close[1..]);
+    } else {
+        res.push_str(open);
+        res.push_str(body);
+        res.push_str(close);
+    }
+    res.push_str(&text[start + body.len()..]);
+}
+
+/// Make it so new text is separated by an empty line
+fn blank_line(res: &mut String) {
+    new_line(res);
+    if !(res.is_empty() || res.ends_with("\n\n")) {
+        res.push('\n');
+    }
+}
+
+/// Make it so new text starts on a new line
+fn new_line(res: &mut String) {
+    if !(res.is_empty() || res.ends_with('\n')) {
+        res.push('\n');
+    }
+}
+
+impl Doc {
+    /// Render doc into AsciiDoc document
+    #[must_use]
+    pub fn render_asciidoc(&self, full: bool) -> String {
+        let mut res = String::new();
+        let mut byte_pos = 0;
+        let mut skip = Skip::default();
+        let mut title_seen = false;
+        let mut def_list = false;
+        let mut code_block = false;
+
+        for (ix, token) in self.tokens.iter().copied().enumerate() {
+            match token {
+                Token::Text { bytes, style } => {
+                    let input = &self.payload[byte_pos..byte_pos + bytes];
+                    byte_pos += bytes;
+                    if skip.enabled() {
+                        continue;
+                    }
+
+                    let mut segment = String::new();
+                    for chunk in split(input) {
+                        match chunk {
+                            Chunk::Raw(input, w) if w == Chunk::CODE || w == Chunk::TICKED_CODE => {
+                                push_styled(&mut res, &segment, style);
+                                segment.clear();
+                                if !code_block {
+                                    blank_line(&mut res);
+                                    res.push_str("----\n");
+                                    code_block = true;
+                                }
+                                if !input.starts_with("```") {
+                                    res.push_str(input);
+                                    res.push('\n');
+                                }
+                            }
+                            Chunk::Raw(input, _) => {
+                                if code_block {
+                                    res.push_str("----\n\n");
+                                    code_block = false;
+                                }
+                                segment.push_str(input);
+                            }
+                            Chunk::Paragraph => {
+                                push_styled(&mut res, &segment, style);
+                                segment.clear();
+                                if full {
+                                    res.push_str(if def_list { "\n+\n" } else { "\n\n" });
+                                } else {
+                                    skip.enable();
+                                    break;
+                                }
+                            }
+                            Chunk::LineBreak => {
+                                push_styled(&mut res, &segment, style);
+                                segment.clear();
+                                res.push_str(" +\n");
+                            }
+                        }
+                    }
+                    push_styled(&mut res, &segment, style);
+                    if code_block {
+                        res.push_str("----\n");
+                        code_block = false;
+                    }
+                }
+                Token::BlockStart(b) => match b {
+                    Block::Header => {
+                        blank_line(&mut res);
+                        if title_seen {
+                            res.push_str("== ");
+                        } else {
+                            res.push_str("= ");
+                            title_seen = true;
+                        }
+                    }
+                    Block::Section3 => {
+                        blank_line(&mut res);
+                        res.push_str("=== ");
+                    }
+                    Block::Section2 | Block::Block | Block::Meta => blank_line(&mut res),
+                    Block::ItemTerm => {
+                        // items with no term such as environment variable info are
+                        // attached to the previous item
+                        if matches!(
+                            self.tokens.get(ix + 1),
+                            Some(Token::BlockEnd(Block::ItemTerm))
+                        ) {
+                            new_line(&mut res);
+                            res.push('+');
+                        } else {
+                            blank_line(&mut res);
+                        }
+                    }
+                    Block::ItemBody => {
+                        if def_list {
+                            if res.ends_with('+') {
+                                res.push('\n');
+                            } else {
+                                res.push_str(":: ");
+                            }
+                        } else {
+                            new_line(&mut res);
+                            res.push_str("* ");
+                        }
+                    }
+                    Block::DefinitionList => {
+                        def_list = true;
+                        new_line(&mut res);
+                    }
+                    Block::Mono | Block::TermRef => {}
+                    Block::InlineBlock => skip.push(),
+                },
+                Token::BlockEnd(b) => match b {
+                    Block::Header | Block::Section2 | Block::Section3 | Block::Block => {
+                        blank_line(&mut res);
+                    }
+                    Block::Meta => new_line(&mut res),
+                    Block::ItemTerm => {
+                        if matches!(
+                            self.tokens.get(ix + 1),
+                            Some(Token::BlockStart(Block::ItemBody))
+                        ) || res.ends_with('+')
+                        {
+                            continue;
+                        }
+                        res.push_str("::");
+                    }
+                    Block::ItemBody => new_line(&mut res),
+                    Block::DefinitionList => {
+                        def_list = false;
+                        blank_line(&mut res);
+                    }
+                    Block::Mono | Block::TermRef => {}
+                    Block::InlineBlock => skip.pop(),
+                },
+            }
+        }
+        res.truncate(res.trim_end().len());
+        res.push('\n');
+        res
+    }
+}
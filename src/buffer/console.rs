@@ -11,7 +11,7 @@
 
 // help might want to render it:
 // - monochrome - default mode
-// - bright/dull/custom colors
+// - bright/dull/custom colors, see Theme
 // - export to markdown and groff
 //
 // monochrome and colors are rendered with different widths so tabstops are out of buffer rendering
@@ -35,95 +35,232 @@ use super::{
     Block, Doc, Skip, Token,
 };
 
-#[cfg(feature = "color")]
 use super::Style;
+use std::sync::atomic::{AtomicU8, Ordering};
 
 const MAX_TAB: usize = 24;
 pub(crate) const MAX_WIDTH: usize = 100;
 
+/// Mapping from [`Style`] to ANSI attributes used to render help and error messages
+///
+/// Each style is rendered with an SGR parameter string such as `"1;31"` for bold red, empty
+/// string means no decorations. Theme is only applied when colors are enabled, see
+/// [`ColorChoice`] for details.
+///
+/// ```rust
+/// # use bpaf::{*, doc::*};
+/// let theme = Theme::bright()
+///     .style(Style::Literal, "1;35")
+///     .style(Style::Invalid, "4;31");
+/// let parser = short('v').switch().to_options().theme(theme);
+/// # drop(parser);
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-/// Default to dull color if colors are enabled,
-#[allow(dead_code)] // not fully used in without colors
-pub(crate) enum Color {
-    Monochrome,
-    #[cfg(feature = "color")]
-    Dull,
-    #[cfg(feature = "color")]
-    Bright,
+pub struct Theme {
+    text: &'static str,
+    emphasis: &'static str,
+    literal: &'static str,
+    metavar: &'static str,
+    invalid: &'static str,
 }
 
-impl Default for Color {
+impl Default for Theme {
+    /// Theme picked by `bright-color` and `dull-color` features, dull one if neither is set
     fn default() -> Self {
-        #![allow(clippy::let_and_return)]
-        #![allow(unused_mut)]
-        #![allow(unused_assignments)]
-        let mut res;
-        #[cfg(not(feature = "color"))]
-        {
-            res = Color::Monochrome;
+        if cfg!(feature = "bright-color") && !cfg!(feature = "dull-color") {
+            Theme::bright()
+        } else {
+            Theme::dull()
         }
+    }
+}
 
-        #[cfg(feature = "color")]
-        {
-            res = Color::Dull;
+impl Theme {
+    /// Theme without any decorations
+    #[must_use]
+    pub const fn monochrome() -> Self {
+        Theme {
+            text: "",
+            emphasis: "",
+            literal: "",
+            metavar: "",
+            invalid: "",
         }
+    }
 
-        #[cfg(feature = "bright-color")]
-        {
-            res = Color::Bright;
+    /// Bold and underlined text only, used by `dull-color` feature
+    #[must_use]
+    pub const fn dull() -> Self {
+        Theme {
+            text: "",
+            emphasis: "1;4",
+            literal: "1",
+            metavar: "4",
+            invalid: "1;31",
         }
+    }
 
-        #[cfg(feature = "dull-color")]
-        {
-            res = Color::Dull;
+    /// Bold colored text, used by `bright-color` feature
+    #[must_use]
+    pub const fn bright() -> Self {
+        Theme {
+            text: "",
+            emphasis: "1;33",
+            literal: "1;32",
+            metavar: "1;34",
+            invalid: "1;31",
         }
+    }
 
-        #[cfg(feature = "color")]
-        {
-            use supports_color::{on, Stream};
-            if !(on(Stream::Stdout).is_some() && on(Stream::Stderr).is_some()) {
-                res = Color::Monochrome;
-            }
+    /// Change SGR parameters used to render a given style, empty string disables decorations
+    #[must_use]
+    pub fn style(mut self, style: Style, sgr: &'static str) -> Self {
+        match style {
+            Style::Text => self.text = sgr,
+            Style::Emphasis => self.emphasis = sgr,
+            Style::Literal => self.literal = sgr,
+            Style::Metavar => self.metavar = sgr,
+            Style::Invalid => self.invalid = sgr,
+        }
+        self
+    }
+
+    fn get(&self, style: Style) -> &'static str {
+        match style {
+            Style::Text => self.text,
+            Style::Emphasis => self.emphasis,
+            Style::Literal => self.literal,
+            Style::Metavar => self.metavar,
+            Style::Invalid => self.invalid,
+        }
+    }
+
+    fn is_monochrome(&self) -> bool {
+        *self == Theme::monochrome()
+    }
+
+    /// Theme to use for terminal output: the one passed if colors are enabled, monochrome otherwise
+    pub(crate) fn resolve(theme: Option<Theme>) -> Theme {
+        if ColorChoice::enabled() {
+            theme.unwrap_or_default()
+        } else {
+            Theme::monochrome()
+        }
+    }
+
+    pub(crate) fn push_str(&self, style: Style, res: &mut String, item: &str) {
+        let sgr = self.get(style);
+        if sgr.is_empty() {
+            res.push_str(item);
+        } else {
+            res.push_str("\x1b[");
+            res.push_str(sgr);
+            res.push('m');
+            res.push_str(item);
+            res.push_str("\x1b[0m");
         }
-        res
     }
 }
 
-#[cfg(feature = "color")]
-impl Color {
-    pub(crate) fn push_str(self, style: Style, res: &mut String, item: &str) {
-        use owo_colors::OwoColorize;
-        use std::fmt::Write;
-        match self {
-            Color::Monochrome => {
-                res.push_str(item);
-                Ok(())
-            }
-            Color::Dull => match style {
-                Style::Text => {
-                    res.push_str(item);
-                    Ok(())
-                }
-                Style::Emphasis => write!(res, "{}", item.underline().bold()),
-                Style::Literal => write!(res, "{}", item.bold()),
-                Style::Metavar => write!(res, "{}", item.underline()),
-                Style::Invalid => write!(res, "{}", item.bold().red()),
-            },
-            Color::Bright => match style {
-                Style::Text => {
-                    res.push_str(item);
-                    Ok(())
+/// When to use colors in help and error messages
+///
+/// With [`ColorChoice::Auto`] `bpaf` follows common conventions: colors are disabled if
+/// `NO_COLOR` is set to a non empty value, forced if `CLICOLOR_FORCE` is set to anything but `0`,
+/// disabled if `CLICOLOR` is `0` and otherwise used only when both `stdout` and `stderr` are
+/// terminals supporting them. Colors are never used unless one of `bright-color` or
+/// `dull-color` features is enabled or colors are forced.
+///
+/// See [`color_choice`](crate::batteries::color_choice) for a ready to use `--color` option.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ColorChoice {
+    /// Decide based on environment variables and terminal capabilities
+    Auto,
+    /// Always use colors
+    Always,
+    /// Never use colors
+    Never,
+}
+
+impl Default for ColorChoice {
+    fn default() -> Self {
+        ColorChoice::Auto
+    }
+}
+
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(0);
+
+impl ColorChoice {
+    /// Use this choice for all the messages printed by `bpaf` from now on
+    ///
+    /// Overrides environment variables unless choice is [`ColorChoice::Auto`]
+    pub fn set_global(self) {
+        let val = match self {
+            ColorChoice::Auto => 0,
+            ColorChoice::Always => 1,
+            ColorChoice::Never => 2,
+        };
+        COLOR_CHOICE.store(val, Ordering::Relaxed);
+    }
+
+    /// Currently active global choice
+    #[must_use]
+    pub fn global() -> Self {
+        match COLOR_CHOICE.load(Ordering::Relaxed) {
+            1 => ColorChoice::Always,
+            2 => ColorChoice::Never,
+            _ => ColorChoice::Auto,
+        }
+    }
+
+    /// Check if colors should be used given the global choice and environment
+    #[must_use]
+    pub fn enabled() -> bool {
+        match ColorChoice::global() {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let var = |name| std::env::var_os(name).filter(|v| !v.is_empty());
+                if var("NO_COLOR").is_some() {
+                    false
+                } else if var("CLICOLOR_FORCE").map_or(false, |v| v != "0") {
+                    true
+                } else if var("CLICOLOR").map_or(false, |v| v == "0") {
+                    false
+                } else {
+                    terminal_supports_color()
                 }
-                Style::Emphasis => write!(res, "{}", item.yellow().bold()),
-                Style::Literal => write!(res, "{}", item.green().bold()),
-                Style::Metavar => write!(res, "{}", item.blue().bold()),
-                Style::Invalid => write!(res, "{}", item.red().bold()),
-            },
+            }
         }
-        .unwrap();
     }
 }
 
+impl std::str::FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "expected one of `auto`, `always` or `never`, got `{}`",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "color")]
+fn terminal_supports_color() -> bool {
+    use supports_color::{on, Stream};
+    on(Stream::Stdout).is_some() && on(Stream::Stderr).is_some()
+}
+
+#[cfg(not(feature = "color"))]
+fn terminal_supports_color() -> bool {
+    false
+}
+
 const PADDING: &str = "                                                  ";
 
 impl Doc {
@@ -133,11 +270,20 @@ impl Doc {
     /// difference for rendered help message, otherwise you can pass `true`.
     #[must_use]
     pub fn monochrome(&self, full: bool) -> String {
-        self.render_console(full, Color::Monochrome, MAX_WIDTH)
+        self.render_console(full, &Theme::monochrome(), MAX_WIDTH)
+    }
+
+    /// Render the document using a given color theme
+    ///
+    /// Unlike messages printed by [`OptionParser::run`](crate::OptionParser::run) theme is applied
+    /// regardless of environment variables and terminal capabilities.
+    #[must_use]
+    pub fn themed(&self, full: bool, theme: &Theme) -> String {
+        self.render_console(full, theme, MAX_WIDTH)
     }
 
     #[allow(clippy::too_many_lines)] // it's a big ass match statement
-    pub(crate) fn render_console(&self, full: bool, color: Color, max_width: usize) -> String {
+    pub(crate) fn render_console(&self, full: bool, theme: &Theme, max_width: usize) -> String {
         let mut res = String::new();
         let mut tabstop = 0;
         let mut byte_pos = 0;
@@ -234,16 +380,7 @@ impl Doc {
                                 pending_blank_line = false;
                                 pending_margin = false;
 
-                                #[cfg(feature = "color")]
-                                {
-                                    color.push_str(style, &mut res, s);
-                                }
-                                #[cfg(not(feature = "color"))]
-                                {
-                                    let _ = style;
-                                    let _ = color;
-                                    res.push_str(s);
-                                }
+                                theme.push_str(style, &mut res, s);
                                 char_pos += w;
                             }
                             Chunk::Paragraph => {
@@ -291,7 +428,7 @@ impl Doc {
                         }
                         Block::DefinitionList | Block::Meta | Block::Mono => {}
                         Block::TermRef => {
-                            if color == Color::Monochrome {
+                            if theme.is_monochrome() {
                                 res.push('`');
                                 char_pos += 1;
                             }
@@ -321,7 +458,7 @@ impl Doc {
                             pending_blank_line = true;
                         }
                         Block::TermRef => {
-                            if color == Color::Monochrome {
+                            if theme.is_monochrome() {
                                 res.push('`');
                                 char_pos += 1;
                             }
@@ -330,7 +467,7 @@ impl Doc {
                 }
             }
         }
-        if pending_newline || pending_blank_line {
+        if (pending_newline || pending_blank_line) && !res.ends_with('\n') {
             res.push('\n');
         }
         #[cfg(test)]
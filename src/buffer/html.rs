@@ -13,9 +13,10 @@ use crate::{
     Parser,
 };
 
+/// Documentation for the whole command tree, `summary` adds a list of links to all the sections
 #[inline(never)]
 #[cfg(feature = "docgen")]
-fn collect_html(app: String, meta: &Meta, info: &Info) -> Doc {
+pub(crate) fn collect_html(app: String, meta: &Meta, info: &Info, summary: bool) -> Doc {
     let mut sections = Vec::new();
     let root = meta;
     let mut path = vec![app];
@@ -23,7 +24,7 @@ fn collect_html(app: String, meta: &Meta, info: &Info) -> Doc {
 
     let mut buf = Doc::default();
 
-    if sections.len() > 1 {
+    if summary && sections.len() > 1 {
         buf.token(Token::BlockStart(Block::Block));
         buf.token(Token::BlockStart(Block::Header));
         buf.text("Command summary");
@@ -64,13 +65,177 @@ impl<T> OptionParser<T> {
     /// Render command line documentation for the app into html/markdown mix
     #[cfg(feature = "docgen")]
     pub fn render_html(&self, app: impl Into<String>) -> String {
-        collect_html(app.into(), &self.inner.meta(), &self.info).render_html(true, false)
+        collect_html(app.into(), &self.inner.meta(), &self.info, true).render_html(true, false)
     }
 
     /// Render command line documentation for the app into Markdown
     #[cfg(feature = "docgen")]
     pub fn render_markdown(&self, app: impl Into<String>) -> String {
-        collect_html(app.into(), &self.inner.meta(), &self.info).render_markdown(true)
+        collect_html(app.into(), &self.inner.meta(), &self.info, true).render_markdown(true)
+    }
+
+    /// Render command line documentation for the app into a set of markdown pages
+    ///
+    /// Returns pairs of file name and page content suitable for [mdBook](https://rust-lang.github.io/mdBook/)
+    /// or a similar static site generator: `SUMMARY.md` with the table of contents followed by
+    /// one page per command named by joining the command path with `-`, for example `app.md`,
+    /// `app-remote.md` and `app-remote-add.md`.
+    ///
+    /// Every option, positional item and command gets a stable anchor such as `#option-verbose`,
+    /// `#positional-name` or `#command-add`, commands link to their own pages.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let add = positional::<String>("NAME")
+    ///     .to_options()
+    ///     .descr("Add a remote")
+    ///     .command("add");
+    /// let parser = construct!([add]).to_options();
+    ///
+    /// let pages = parser.render_mdbook("app");
+    /// let names = pages.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(names, ["SUMMARY.md", "app.md", "app-add.md"]);
+    /// assert_eq!(pages[0].1, "# Summary\n\n- [app](app.md)\n    - [app add](app-add.md)\n");
+    /// ```
+    #[cfg(feature = "docgen")]
+    pub fn render_mdbook(&self, app: impl Into<String>) -> Vec<(String, String)> {
+        let meta = self.inner.meta();
+        let mut sections = Vec::new();
+        let mut path = vec![app.into()];
+        extract_sections(&meta, &self.info, &mut path, &mut sections);
+
+        let page_name = |path: &[String]| format!("{}.md", path.join("-"));
+
+        let mut summary = String::from("# Summary\n\n");
+        let mut res = Vec::new();
+        for section in &sections {
+            let depth = section.path.len();
+            summary.push_str(&"    ".repeat(depth - 1));
+            summary.push_str(&format!(
+                "- [{}]({})\n",
+                section.path.join(" "),
+                page_name(&section.path)
+            ));
+
+            let mut buf = Doc::default();
+            buf.token(Token::BlockStart(Block::Header));
+            buf.text(&section.path.join(" "));
+            buf.token(Token::BlockEnd(Block::Header));
+            buf.doc(&render_help(
+                &section.path,
+                section.info,
+                section.meta,
+                &section.info.meta(),
+                false,
+            ));
+
+            let pages = sections
+                .iter()
+                .filter(|s| s.path.len() == depth + 1 && s.path.starts_with(&section.path))
+                .map(|s| (s.path[depth].clone(), page_name(&s.path)))
+                .collect::<Vec<_>>();
+            let links = Links::new(&buf, &pages);
+            res.push((
+                page_name(&section.path),
+                buf.render_markdown_links(true, Some(&links)),
+            ));
+        }
+        res.insert(0, ("SUMMARY.md".to_owned(), summary));
+        res
+    }
+}
+
+/// Anchors and link targets for a single page of a multi page documentation
+#[cfg(feature = "docgen")]
+pub(crate) struct Links {
+    /// anchor for every item term and a page it links to, indexed by the position of the
+    /// starting token
+    anchors: Vec<(usize, String, Option<String>)>,
+    /// names of documented items and where they should link to
+    targets: Vec<(String, String)>,
+}
+
+#[cfg(feature = "docgen")]
+impl Links {
+    /// Collect anchors from all the item terms in a document
+    ///
+    /// Options are referred to by their names, positional items by their metavars, commands
+    /// link to pages listed in `pages` if present
+    fn new(doc: &Doc, pages: &[(String, String)]) -> Self {
+        let mut links = Links {
+            anchors: Vec::new(),
+            targets: Vec::new(),
+        };
+        for (ix, parts) in doc.item_terms() {
+            let literals = parts
+                .iter()
+                .filter(|p| p.1 == Style::Literal)
+                // long only names are padded to line up with short ones
+                .map(|p| p.0.trim())
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>();
+            let anchor = if let Some(first) = literals.first() {
+                if first.starts_with('-') {
+                    let name = literals
+                        .iter()
+                        .find(|l| l.starts_with("--"))
+                        .unwrap_or(first);
+                    format!("option-{}", name.trim_start_matches('-'))
+                } else {
+                    format!("command-{}", first)
+                }
+            } else if let Some(metavar) = parts.iter().find(|p| p.1 == Style::Metavar) {
+                format!("positional-{}", metavar.0.to_lowercase())
+            } else {
+                continue;
+            };
+            let page = pages
+                .iter()
+                .find(|p| literals.first() == Some(&p.0.as_str()))
+                .map(|p| p.1.clone());
+            for name in &literals {
+                let target = page.clone().unwrap_or_else(|| format!("#{}", anchor));
+                links.targets.push(((*name).to_owned(), target));
+            }
+            if literals.is_empty() {
+                for (metavar, _) in parts.iter().filter(|p| p.1 == Style::Metavar) {
+                    links
+                        .targets
+                        .push(((*metavar).to_owned(), format!("#{}", anchor)));
+                }
+            }
+            links.anchors.push((ix, anchor, page));
+        }
+        links
+    }
+
+    fn anchor(&self, ix: usize) -> Option<(&str, Option<&str>)> {
+        self.anchors
+            .iter()
+            .find(|a| a.0 == ix)
+            .map(|a| (a.1.as_str(), a.2.as_deref()))
+    }
+
+    fn target(&self, name: &str) -> Option<&str> {
+        self.targets
+            .iter()
+            .find(|t| t.0 == name)
+            .map(|t| t.1.as_str())
+    }
+}
+
+/// Links are only collected for multi page documentation, without `docgen` there are none
+#[cfg(not(feature = "docgen"))]
+pub(crate) enum Links {}
+
+#[cfg(not(feature = "docgen"))]
+impl Links {
+    fn anchor(&self, _ix: usize) -> Option<(&str, Option<&str>)> {
+        match *self {}
+    }
+
+    fn target(&self, _name: &str) -> Option<&str> {
+        match *self {}
     }
 }
 
@@ -310,6 +475,48 @@ impl Doc {
     /// Render doc into markdown document, used by documentation sample generator
     #[must_use]
     pub fn render_markdown(&self, full: bool) -> String {
+        self.render_markdown_links(full, None)
+    }
+
+    /// Text and style of every item term, paired with the position of the starting token
+    #[cfg(feature = "docgen")]
+    fn item_terms(&self) -> Vec<(usize, Vec<(&str, Style)>)> {
+        let mut res = Vec::new();
+        let mut byte_pos = 0;
+        let mut current: Option<(usize, Vec<(&str, Style)>)> = None;
+        for (ix, token) in self.tokens.iter().copied().enumerate() {
+            match token {
+                Token::Text { bytes, style } => {
+                    if let Some((_, parts)) = &mut current {
+                        parts.push((&self.payload[byte_pos..byte_pos + bytes], style));
+                    }
+                    byte_pos += bytes;
+                }
+                Token::BlockStart(Block::ItemTerm) => current = Some((ix, Vec::new())),
+                Token::BlockEnd(Block::ItemTerm) => res.extend(current.take()),
+                Token::BlockStart(_) | Token::BlockEnd(_) => {}
+            }
+        }
+        res
+    }
+
+    /// Text inside of a block starting at a given token
+    fn block_text(&self, start: usize, mut byte_pos: usize) -> String {
+        let mut res = String::new();
+        for token in self.tokens[start + 1..].iter().copied() {
+            match token {
+                Token::Text { bytes, .. } => {
+                    res.push_str(&self.payload[byte_pos..byte_pos + bytes]);
+                    byte_pos += bytes;
+                }
+                Token::BlockEnd(_) => break,
+                Token::BlockStart(_) => {}
+            }
+        }
+        res
+    }
+
+    fn render_markdown_links(&self, full: bool, links: Option<&Links>) -> String {
         let mut res = String::new();
         let mut byte_pos = 0;
         let mut cur_style = Styles::default();
@@ -320,6 +527,8 @@ impl Doc {
         let mut def_list = false;
         let mut code_block = false;
         let mut app_name_seen = false;
+        let mut link_targets = Vec::new();
+        let mut term_link = None;
         for (ix, token) in self.tokens.iter().copied().enumerate() {
             match token {
                 Token::Text { bytes, style } => {
@@ -402,6 +611,13 @@ impl Doc {
                                 Some(Token::BlockEnd(Block::ItemTerm))
                             );
                             res.push_str(if empty_term { "  " } else { "- " });
+                            if let Some((anchor, page)) = links.and_then(|l| l.anchor(ix)) {
+                                res.push_str(&format!("<a id=\"{}\"></a>", anchor));
+                                if page.is_some() {
+                                    res.push('[');
+                                }
+                                term_link = page;
+                            }
                         }
                         Block::ItemBody => {
                             if def_list {
@@ -422,7 +638,14 @@ impl Doc {
                             mono += 1;
                         }
                         Block::Section3 => res.push_str("### "),
-                        Block::TermRef => {}
+                        Block::TermRef => {
+                            let target =
+                                links.and_then(|l| l.target(&self.block_text(ix, byte_pos)));
+                            if target.is_some() {
+                                res.push('[');
+                            }
+                            link_targets.push(target);
+                        }
                         Block::InlineBlock => {
                             skip.push();
                         }
@@ -438,7 +661,16 @@ impl Doc {
                         Block::InlineBlock => {
                             skip.pop();
                         }
-                        Block::ItemTerm | Block::TermRef => {}
+                        Block::ItemTerm => {
+                            if let Some(page) = term_link.take() {
+                                res.push_str(&format!("]({})", page));
+                            }
+                        }
+                        Block::TermRef => {
+                            if let Some(Some(target)) = link_targets.pop() {
+                                res.push_str(&format!("]({})", target));
+                            }
+                        }
                         Block::ItemBody => {
                             if def_list {
                                 res.push('\n');
@@ -476,4 +708,30 @@ mod tests {
 
         assert_eq!(r, "<b>Usage: </b><tt><b>my_program</b></tt>")
     }
+
+    #[cfg(feature = "docgen")]
+    #[test]
+    fn term_refs_become_links() {
+        let mut doc = Doc::default();
+        doc.text("Use ");
+        doc.token(Token::BlockStart(Block::TermRef));
+        doc.literal("--verbose");
+        doc.token(Token::BlockEnd(Block::TermRef));
+        doc.text(" or ");
+        doc.token(Token::BlockStart(Block::TermRef));
+        doc.literal("--quiet");
+        doc.token(Token::BlockEnd(Block::TermRef));
+        doc.token(Token::BlockStart(Block::DefinitionList));
+        doc.token(Token::BlockStart(Block::ItemTerm));
+        doc.literal("--verbose");
+        doc.token(Token::BlockEnd(Block::ItemTerm));
+        doc.token(Token::BlockEnd(Block::DefinitionList));
+
+        let links = Links::new(&doc, &[]);
+        let r = doc.render_markdown_links(true, Some(&links));
+        assert_eq!(
+            r,
+            "Use [**`--verbose`**](#option-verbose) or **`--quiet`**\n- <a id=\"option-verbose\"></a>**`--verbose`**\n"
+        );
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
-    buffer::{extract_sections, manpage::escape::Apostrophes, Block, HelpItems, Style, Token},
-    Doc, OptionParser, Parser,
+    buffer::{
+        extract_sections, manpage::escape::Apostrophes, Block, HelpItem, HelpItems, Style, Token,
+    },
+    info::Info,
+    Doc, Meta, OptionParser, Parser,
 };
 
 mod escape;
@@ -135,6 +138,8 @@ impl<T> OptionParser<T> {
             }
         }
 
+        write_trailing_sections(&mut buf, &self.info, sections.iter().map(|s| s.meta));
+
         let mut manpage = Roff::new();
         manpage.control(
             "TH",
@@ -151,6 +156,212 @@ impl<T> OptionParser<T> {
 
         buf.render_roff(manpage)
     }
+
+    /// Render command line documentation for the app into a set of manpages, one per command
+    ///
+    /// Returns pairs of file name and manpage content: the first page documents the app itself
+    /// and is named after `app`, pages for commands are named by joining the command path with
+    /// `-`, for example `tool.1`, `tool-build.1` and `tool-remote-add.1`. Each page contains its
+    /// own `NAME`, `SYNOPSIS`, `DESCRIPTION` and `OPTIONS` sections and `SEE ALSO` section
+    /// referring to parent and child pages.
+    ///
+    /// ```rust
+    /// # use bpaf::{*, doc::Section};
+    /// let add = positional::<String>("NAME")
+    ///     .to_options()
+    ///     .descr("Add a remote")
+    ///     .command("add");
+    /// let remote = construct!([add])
+    ///     .to_options()
+    ///     .descr("Manage remotes")
+    ///     .command("remote");
+    /// let parser = construct!([remote]).to_options().descr("Version control");
+    ///
+    /// let pages = parser.render_manpages("tool", Section::General, None, None, None);
+    /// let names = pages.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(names, ["tool.1", "tool-remote.1", "tool-remote-add.1"]);
+    /// ```
+    pub fn render_manpages(
+        &self,
+        app: impl AsRef<str>,
+        section: Section,
+        last_update_date: Option<&str>,
+        vendor: Option<&str>,
+        application_title: Option<&str>,
+    ) -> Vec<(String, String)> {
+        let mut sections = Vec::new();
+        let root = self.inner.meta();
+        let mut path = vec![app.as_ref().to_string()];
+
+        extract_sections(&root, &self.info, &mut path, &mut sections);
+
+        let page_name = |path: &[String]| path.join("-");
+
+        let mut res = Vec::new();
+        for doc_section in &sections {
+            let name = page_name(&doc_section.path);
+            let mut buf = Doc::default();
+
+            write_header(&mut buf, "NAME");
+            buf.text(&name);
+            if let Some(descr) = &doc_section.info.descr {
+                buf.text(" - ");
+                buf.doc(descr);
+            }
+
+            write_header(&mut buf, "SYNOPSIS");
+            buf.write_path(&doc_section.path);
+            buf.write_meta(doc_section.meta, true);
+
+            if let Some(header) = &doc_section.info.header {
+                write_header(&mut buf, "DESCRIPTION");
+                buf.token(Token::BlockStart(Block::Block));
+                buf.doc(header);
+                buf.token(Token::BlockEnd(Block::Block));
+            }
+
+            write_header(&mut buf, "OPTIONS");
+            let mut items = HelpItems::default();
+            items.append_meta(doc_section.meta);
+            let help_meta = doc_section.info.meta();
+            items.append_meta(&help_meta);
+            buf.write_help_item_groups(items, false);
+
+            if let Some(footer) = &doc_section.info.footer {
+                buf.token(Token::BlockStart(Block::Block));
+                buf.doc(footer);
+                buf.token(Token::BlockEnd(Block::Block));
+            }
+
+            write_trailing_sections(&mut buf, doc_section.info, [doc_section.meta]);
+
+            // parent page first, children pages in the order they are declared
+            let depth = doc_section.path.len();
+            let parent = depth
+                .checked_sub(1)
+                .filter(|len| *len > 0)
+                .map(|len| &doc_section.path[..len]);
+            let related = parent
+                .into_iter()
+                .chain(
+                    sections
+                        .iter()
+                        .map(|s| s.path.as_slice())
+                        .filter(|p| p.len() == depth + 1 && p.starts_with(&doc_section.path)),
+                )
+                .collect::<Vec<_>>();
+            if !related.is_empty() {
+                write_header(&mut buf, "SEE ALSO");
+                for (ix, path) in related.iter().enumerate() {
+                    if ix > 0 {
+                        buf.text(", ");
+                    }
+                    buf.literal(&page_name(path));
+                    buf.text("(");
+                    buf.text(section.as_str());
+                    buf.text(")");
+                }
+            }
+
+            let mut manpage = Roff::new();
+            manpage.control(
+                "TH",
+                [
+                    name.as_str(),
+                    section.as_str(),
+                    last_update_date.unwrap_or("-"),
+                    vendor.unwrap_or("-"),
+                    application_title.unwrap_or(""),
+                ]
+                .iter()
+                .copied(),
+            );
+
+            let file_name = format!("{}.{}", name, section.as_str());
+            res.push((file_name, buf.render_roff(manpage)));
+        }
+        res
+    }
+}
+
+fn write_header(buf: &mut Doc, title: &str) {
+    buf.token(Token::BlockStart(Block::Header));
+    buf.text(title);
+    buf.token(Token::BlockEnd(Block::Header));
+}
+
+fn write_definition(buf: &mut Doc, term: &str, style: Style, body: Option<&Doc>) {
+    buf.token(Token::BlockStart(Block::ItemTerm));
+    buf.write_str(term, style);
+    buf.token(Token::BlockEnd(Block::ItemTerm));
+    if let Some(body) = body {
+        buf.token(Token::BlockStart(Block::ItemBody));
+        buf.doc(body);
+        buf.token(Token::BlockEnd(Block::ItemBody));
+    }
+}
+
+/// EXIT STATUS, ENVIRONMENT, FILES, EXAMPLES, AUTHORS and REPORTING BUGS sections
+///
+/// ENVIRONMENT lists every environment variable used by items in `metas`, the rest comes
+/// from the metadata set on [`OptionParser`]
+fn write_trailing_sections<'a>(
+    buf: &mut Doc,
+    info: &Info,
+    metas: impl IntoIterator<Item = &'a Meta>,
+) {
+    if !info.exit_statuses.is_empty() {
+        write_header(buf, "EXIT STATUS");
+        for (code, descr) in &info.exit_statuses {
+            write_definition(buf, &code.to_string(), Style::Literal, Some(descr));
+        }
+    }
+
+    let mut items = HelpItems::default();
+    for meta in metas {
+        items.append_meta(meta);
+    }
+    let mut envs: Vec<(&str, Option<&Doc>)> = Vec::new();
+    for item in &items.items {
+        if let HelpItem::Flag { env, help, .. } | HelpItem::Argument { env, help, .. } = item {
+            for env in env.iter() {
+                if envs.iter().all(|(name, _)| name != env) {
+                    envs.push((env, *help));
+                }
+            }
+        }
+    }
+    if !envs.is_empty() {
+        write_header(buf, "ENVIRONMENT");
+        for (env, help) in envs {
+            write_definition(buf, env, Style::Literal, help);
+        }
+    }
+
+    if !info.files.is_empty() {
+        write_header(buf, "FILES");
+        for (path, descr) in &info.files {
+            write_definition(buf, path, Style::Literal, Some(descr));
+        }
+    }
+
+    if !info.examples.is_empty() {
+        write_header(buf, "EXAMPLES");
+        for (cmdline, descr) in &info.examples {
+            write_definition(buf, cmdline, Style::Literal, Some(descr));
+        }
+    }
+
+    if let Some(authors) = &info.authors {
+        write_header(buf, "AUTHORS");
+        buf.doc(authors);
+    }
+
+    if let Some(url) = info.bug_url {
+        write_header(buf, "REPORTING BUGS");
+        buf.text("Report bugs at ");
+        buf.literal(url);
+    }
 }
 
 impl From<Style> for Font {
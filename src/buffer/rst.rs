@@ -0,0 +1,238 @@
+use crate::{
+    buffer::{
+        html::collect_html,
+        splitter::{split, Chunk},
+        Block, Skip, Style, Token,
+    },
+    Doc, OptionParser, Parser,
+};
+
+impl<T> OptionParser<T> {
+    /// Render command line documentation for the app into reStructuredText
+    ///
+    /// Documentation covers the whole command tree, one section per command
+    pub fn render_rst(&self, app: impl Into<String>) -> String {
+        collect_html(app.into(), &self.inner.meta(), &self.info, false).render_rst(true)
+    }
+}
+
+/// Inline markup in reST must be separated from surrounding text
+fn needs_escape_before(prev: Option<char>) -> bool {
+    prev.map_or(false, |c| !(c.is_whitespace() || "-:/'\"<([{".contains(c)))
+}
+
+fn needs_escape_after(next: Option<char>) -> bool {
+    next.map_or(false, |c| {
+        !(c.is_whitespace() || "-.,:;!?\\/'\")]}>".contains(c))
+    })
+}
+
+struct Writer {
+    res: String,
+    /// inline markup was just closed, next text might need to be separated from it
+    after_markup: bool,
+    /// current indentation for definition bodies
+    indent: &'static str,
+}
+
+impl Writer {
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.after_markup && needs_escape_after(text.chars().next()) {
+            self.res.push_str("\\ ");
+        }
+        self.after_markup = false;
+        for c in text.chars() {
+            if matches!(c, '*' | '`' | '|' | '\\') {
+                self.res.push('\\');
+            }
+            self.res.push(c);
+        }
+    }
+
+    /// Wrap non blank part of the text into inline markup for a given style
+    fn push_styled(&mut self, text: &str, style: Style) {
+        // leading whitespace would change indentation
+        let text = if self.res.trim_end_matches(' ').ends_with('\n') || self.res.is_empty() {
+            text.trim_start()
+        } else {
+            text
+        };
+        let (open, close) = match style {
+            Style::Text => return self.push_text(text),
+            Style::Emphasis | Style::Invalid => ("**", "**"),
+            Style::Literal => ("``", "``"),
+            Style::Metavar => ("*", "*"),
+        };
+        let body = text.trim();
+        if body.is_empty() {
+            return self.push_text(text);
+        }
+        let start = text.len() - text.trim_start().len();
+        self.push_text(&text[..start]);
+        if self.after_markup || needs_escape_before(self.res.chars().last()) {
+            self.res.push_str("\\ ");
+        }
+        self.res.push_str(open);
+        self.res.push_str(body);
+        self.res.push_str(close);
+        self.after_markup = true;
+        self.push_text(&text[start + body.len()..]);
+    }
+
+    /// Make it so new text is separated by an empty line
+    fn blank_line(&mut self) {
+        self.new_line();
+        if !(self.res.is_empty() || self.res.ends_with("\n\n")) {
+            self.res.push('\n');
+        }
+    }
+
+    /// Make it so new text starts on a new line
+    fn new_line(&mut self) {
+        self.after_markup = false;
+        if !(self.res.is_empty() || self.res.ends_with('\n')) {
+            self.res.push('\n');
+        }
+    }
+}
+
+impl Doc {
+    /// Render doc into reStructuredText document
+    #[must_use]
+    pub fn render_rst(&self, full: bool) -> String {
+        let mut w = Writer {
+            res: String::new(),
+            after_markup: false,
+            indent: "",
+        };
+        let mut byte_pos = 0;
+        let mut skip = Skip::default();
+        let mut title_seen = false;
+        let mut code_block = false;
+        // section titles are underlined so they are collected first
+        let mut title: Option<String> = None;
+
+        for token in self.tokens.iter().copied() {
+            match token {
+                Token::Text { bytes, style } => {
+                    let input = &self.payload[byte_pos..byte_pos + bytes];
+                    byte_pos += bytes;
+                    if skip.enabled() {
+                        continue;
+                    }
+                    if let Some(title) = &mut title {
+                        title.push_str(input);
+                        continue;
+                    }
+
+                    let mut segment = String::new();
+                    for chunk in split(input) {
+                        match chunk {
+                            Chunk::Raw(input, width)
+                                if width == Chunk::CODE || width == Chunk::TICKED_CODE =>
+                            {
+                                w.push_styled(&segment, style);
+                                segment.clear();
+                                if !code_block {
+                                    w.blank_line();
+                                    w.res.push_str(w.indent);
+                                    w.res.push_str("::\n\n");
+                                    code_block = true;
+                                }
+                                if !input.starts_with("```") {
+                                    w.res.push_str(w.indent);
+                                    w.res.push_str("    ");
+                                    w.res.push_str(input);
+                                    w.res.push('\n');
+                                }
+                            }
+                            Chunk::Raw(input, _) => {
+                                if code_block {
+                                    w.blank_line();
+                                    w.res.push_str(w.indent);
+                                    code_block = false;
+                                }
+                                segment.push_str(input);
+                            }
+                            Chunk::Paragraph => {
+                                w.push_styled(&segment, style);
+                                segment.clear();
+                                if full {
+                                    w.blank_line();
+                                    w.res.push_str(w.indent);
+                                } else {
+                                    skip.enable();
+                                    break;
+                                }
+                            }
+                            Chunk::LineBreak => {
+                                w.push_styled(&segment, style);
+                                segment.clear();
+                                w.new_line();
+                                w.res.push_str(w.indent);
+                            }
+                        }
+                    }
+                    w.push_styled(&segment, style);
+                    if code_block {
+                        w.blank_line();
+                        code_block = false;
+                    }
+                }
+                Token::BlockStart(b) => match b {
+                    Block::Header | Block::Section3 => {
+                        w.blank_line();
+                        title = Some(String::new());
+                    }
+                    Block::Section2 | Block::Block | Block::Meta => w.blank_line(),
+                    Block::ItemTerm => w.blank_line(),
+                    Block::ItemBody => {
+                        if w.indent.is_empty() {
+                            w.new_line();
+                            w.res.push_str("* ");
+                        } else {
+                            w.new_line();
+                            w.res.push_str(w.indent);
+                        }
+                    }
+                    Block::DefinitionList => {
+                        w.blank_line();
+                        w.indent = "    ";
+                    }
+                    Block::Mono | Block::TermRef => {}
+                    Block::InlineBlock => skip.push(),
+                },
+                Token::BlockEnd(b) => match b {
+                    Block::Header | Block::Section3 => {
+                        let text = title.take().unwrap_or_default();
+                        let underline = match b {
+                            Block::Header if title_seen => '-',
+                            Block::Header => '=',
+                            _ => '~',
+                        };
+                        title_seen |= b == Block::Header;
+                        w.res.push_str(&text);
+                        w.res.push('\n');
+                        w.res
+                            .extend(std::iter::repeat(underline).take(text.chars().count()));
+                        w.blank_line();
+                    }
+                    Block::Section2 | Block::Block => w.blank_line(),
+                    Block::Meta | Block::ItemTerm | Block::ItemBody => w.new_line(),
+                    Block::DefinitionList => {
+                        w.indent = "";
+                        w.blank_line();
+                    }
+                    Block::Mono | Block::TermRef => {}
+                    Block::InlineBlock => skip.pop(),
+                },
+            }
+        }
+        w.res.truncate(w.res.trim_end().len());
+        w.res.push('\n');
+        w.res
+    }
+}
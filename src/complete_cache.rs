@@ -0,0 +1,306 @@
+//! Time limits and on-disk cache for slow completion functions
+//!
+//! Completion functions that talk to other services can take a while or hang, and the shell
+//! waits for them on every TAB. [`ParseComp::timeout`] gives up on such function and
+//! [`ParseComp::cache`] reuses values it produced earlier for the same partial input.
+
+use crate::{
+    complete_gen::{escape, unescape},
+    structs::{eval_comp, ParseComp},
+    Error, Meta, Parser, State,
+};
+use std::{
+    path::PathBuf,
+    sync::{mpsc, Arc},
+    time::Duration,
+};
+
+/// Values produced by a completion function, with optional descriptions
+type Values = Vec<(String, Option<String>)>;
+
+/// On-disk cache for values produced by a completion function
+///
+/// Values are stored per partial input and reused until they are older than `ttl`. Stale values
+/// are still used when completion function doesn't finish in time, see
+/// [`timeout`](crate::parsers::ParseCompLimit::timeout). Failures to read or write the cache
+/// are ignored.
+///
+/// To drop cached values when they are known to be outdated, for example after the app creates
+/// a new branch, create the same cache and call [`invalidate`](Self::invalidate).
+#[derive(Debug, Clone)]
+pub struct CompletionCache {
+    dir: Option<PathBuf>,
+    ttl: Duration,
+}
+
+impl CompletionCache {
+    /// Cache in per user cache directory
+    ///
+    /// Values are stored in `$XDG_CACHE_HOME/<app>/completion/<name>`, `~/.cache` is used if
+    /// `XDG_CACHE_HOME` is not set and `%LOCALAPPDATA%` on Windows. `name` should be unique
+    /// for each completion function in the app.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `app` or `name` is empty, `..` or contains a path separator
+    #[must_use]
+    #[track_caller]
+    pub fn new(app: &str, name: &str, ttl: Duration) -> Self {
+        for part in [app, name] {
+            assert!(
+                !(part.is_empty() || part.contains("..") || part.contains(['/', '\\'])),
+                "{:?} can't be used as a cache directory name",
+                part
+            );
+        }
+        // relative XDG paths are invalid and should be ignored
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))
+            .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from));
+        Self {
+            dir: base.map(|base| base.join(app).join("completion").join(name)),
+            ttl,
+        }
+    }
+
+    /// Cache in a given directory
+    #[must_use]
+    pub fn in_dir(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            dir: Some(dir.into()),
+            ttl,
+        }
+    }
+
+    /// Remove all cached values, next completion calls the function again
+    ///
+    /// Only files created by the cache are removed, the directory itself goes away if nothing
+    /// else is left in it.
+    ///
+    /// # Errors
+    /// Fails if cache directory exists but can't be read or cached values can't be removed
+    pub fn invalidate(&self) -> std::io::Result<()> {
+        let dir = match &self.dir {
+            Some(dir) if dir.exists() => dir,
+            _ => return Ok(()),
+        };
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_name().to_str().map_or(false, is_entry) {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+        // fails if something else lives there
+        let _ = std::fs::remove_dir(dir);
+        Ok(())
+    }
+
+    /// File with values for a given partial input
+    fn file(&self, key: &str) -> Option<PathBuf> {
+        use std::fmt::Write;
+        // partial input can contain anything, including path separators
+        let mut name = String::from("k");
+        for b in key.bytes() {
+            write!(name, "{:02x}", b).ok()?;
+        }
+        Some(self.dir.as_ref()?.join(name))
+    }
+
+    /// Cached values for a partial input and if they are still fresh
+    fn load(&self, key: &str) -> Option<(Values, bool)> {
+        let file = self.file(key)?;
+        let modified = std::fs::metadata(&file).ok()?.modified().ok()?;
+        let contents = std::fs::read_to_string(&file).ok()?;
+        let values = contents
+            .lines()
+            .map(|line| {
+                let mut fields = line.split('\t').skip(1).map(unescape);
+                (fields.next().unwrap_or_default(), fields.next())
+            })
+            .collect();
+        // file from the future is fresh
+        let fresh = modified.elapsed().map_or(true, |age| age < self.ttl);
+        Some((values, fresh))
+    }
+
+    fn store(&self, key: &str, values: &[(String, Option<String>)]) {
+        let (file, dir) = match (self.file(key), &self.dir) {
+            (Some(file), Some(dir)) => (file, dir),
+            _ => return,
+        };
+        let mut contents = String::new();
+        for (value, help) in values {
+            escape(&mut contents, value);
+            if let Some(help) = help {
+                escape(&mut contents, help);
+            }
+            contents.push('\n');
+        }
+        // several shells can complete at once, readers should never see a partial file
+        let tmp = file.with_extension(format!("{}.tmp", std::process::id()));
+        if std::fs::create_dir_all(dir).is_ok() && std::fs::write(&tmp, contents).is_ok() {
+            let _ = std::fs::rename(&tmp, &file);
+        }
+    }
+}
+
+/// File name written by [`CompletionCache::store`]: `k` followed by hex encoded key, with
+/// `.<pid>.tmp` for files that are not renamed yet
+fn is_entry(name: &str) -> bool {
+    let hex = |s: &str| s.bytes().all(|b| b.is_ascii_hexdigit());
+    let pid = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match name.strip_prefix('k') {
+        Some(name) => match name.split_once('.') {
+            Some((key, rest)) => hex(key) && rest.strip_suffix(".tmp").map_or(false, pid),
+            None => hex(name),
+        },
+        None => false,
+    }
+}
+
+impl<P, F> ParseComp<P, F> {
+    /// Give up on completion function that runs longer than `limit`
+    ///
+    /// Completion function runs in a separate thread, when it doesn't finish in time completion
+    /// uses stale values from the [`cache`](Self::cache) if there are any or offers nothing
+    /// otherwise. The thread is not stopped and keeps running in the background.
+    #[must_use]
+    pub fn timeout(self, limit: Duration) -> ParseCompLimit<P, F> {
+        ParseCompLimit::from(self).timeout(limit)
+    }
+
+    /// Cache values produced by completion function on disk
+    ///
+    /// See [`CompletionCache`] for details
+    #[must_use]
+    pub fn cache(self, cache: CompletionCache) -> ParseCompLimit<P, F> {
+        ParseCompLimit::from(self).cache(cache)
+    }
+}
+
+/// Parser that limits how long completion function runs and caches its results, created with
+/// [`timeout`](ParseComp::timeout) or [`cache`](ParseComp::cache)
+pub struct ParseCompLimit<P, F> {
+    inner: P,
+    op: Arc<F>,
+    group: Option<String>,
+    timeout: Option<Duration>,
+    cache: Option<CompletionCache>,
+}
+
+impl<P, F> From<ParseComp<P, F>> for ParseCompLimit<P, F> {
+    fn from(comp: ParseComp<P, F>) -> Self {
+        Self {
+            inner: comp.inner,
+            op: Arc::new(comp.op),
+            group: comp.group,
+            timeout: None,
+            cache: None,
+        }
+    }
+}
+
+impl<P, F> ParseCompLimit<P, F> {
+    #[must_use]
+    /// Attach group name to parsed values
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Give up on completion function that runs longer than `limit`
+    ///
+    /// See [`ParseComp::timeout`]
+    #[must_use]
+    pub fn timeout(mut self, limit: Duration) -> Self {
+        self.timeout = Some(limit);
+        self
+    }
+
+    /// Cache values produced by completion function on disk
+    ///
+    /// See [`CompletionCache`]
+    #[must_use]
+    pub fn cache(mut self, cache: CompletionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Run completion function, `None` if it didn't finish in time
+    fn run<T, M>(&self, input: &T) -> Option<Values>
+    where
+        T: Clone + Send + 'static,
+        M: Into<String>,
+        F: Fn(&T) -> Vec<(M, Option<M>)> + Send + Sync + 'static,
+    {
+        let op = self.op.clone();
+        let run = move |input: &T| -> Values {
+            op(input)
+                .into_iter()
+                .map(|(value, help)| (value.into(), help.map(Into::into)))
+                .collect()
+        };
+        let limit = match self.timeout {
+            Some(limit) => limit,
+            None => return Some(run(input)),
+        };
+        let (tx, rx) = mpsc::channel();
+        let input = input.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(run(&input));
+        });
+        rx.recv_timeout(limit).ok()
+    }
+
+    fn suggest<T, M>(&self, input: &T, key: &str) -> Values
+    where
+        T: Clone + Send + 'static,
+        M: Into<String>,
+        F: Fn(&T) -> Vec<(M, Option<M>)> + Send + Sync + 'static,
+    {
+        let cached = self.cache.as_ref().and_then(|cache| cache.load(key));
+        if let Some((values, true)) = cached {
+            return values;
+        }
+        match self.run(input) {
+            Some(values) => {
+                if let Some(cache) = &self.cache {
+                    cache.store(key, &values);
+                }
+                values
+            }
+            // stale values are better than nothing
+            None => cached.map(|(values, _)| values).unwrap_or_default(),
+        }
+    }
+}
+
+impl<P, T, F, M> Parser<T> for ParseCompLimit<P, F>
+where
+    P: Parser<T> + Sized,
+    T: Clone + Send + 'static,
+    M: Into<String>,
+    F: Fn(&T) -> Vec<(M, Option<M>)> + Send + Sync + 'static,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        // values are cached by the word being completed
+        let key = args
+            .items
+            .last()
+            .and_then(|arg| arg.os_str().to_str())
+            .unwrap_or_default()
+            .to_owned();
+        eval_comp(
+            &self.inner,
+            &self.group,
+            |input, _| self.suggest(input, &key),
+            args,
+        )
+    }
+
+    fn meta(&self) -> Meta {
+        Meta::Comp(Box::new(self.inner.meta()), None)
+    }
+}
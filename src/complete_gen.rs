@@ -16,12 +16,216 @@
 
 use crate::{
     args::{Arg, State},
-    complete_shell::{render_bash, render_fish, render_simple, render_test, render_zsh},
+    complete_shell::{
+        render_bash, render_fish, render_nushell, render_powershell, render_simple, render_test,
+        render_zsh,
+    },
     item::ShortLong,
+    meta_youmean::damerau_levenshtein,
     parsers::NamedArg,
-    Doc, ShellComp,
+    Doc, ShellComp, TypeHint,
 };
-use std::ffi::OsStr;
+use std::{borrow::Cow, ffi::OsStr, rc::Rc};
+
+/// How shell completion matches partially typed names of flags, arguments and commands
+///
+/// See [`completion_matcher`](crate::OptionParser::completion_matcher)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionMatcher {
+    /// Names must start with the typed text, this is the default
+    Prefix,
+    /// Case insensitive prefix, substring and subsequence matches ordered by how good the
+    /// match is, names with small typos are offered when nothing else matches
+    Fuzzy,
+}
+
+/// Completion candidate, see [`completions`](crate::OptionParser::completions)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Completion {
+    /// Text to replace the word under cursor with, empty for placeholders such as metavariables
+    /// and for file hints
+    pub text: String,
+    /// Text to show to the user, can include a metavariable
+    pub display: String,
+    /// First line of the help message
+    pub description: Option<String>,
+    /// Group name for items with a [`group_help`](crate::Parser::group_help) or a command group
+    pub group: Option<String>,
+    /// What this candidate stands for
+    pub kind: CompletionKind,
+}
+
+/// What [`Completion`] stands for
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompletionKind {
+    /// Flag or argument name
+    Flag,
+    /// Command name
+    Command,
+    /// Value for an argument or a positional item
+    Value,
+    /// File or directory name, with an optional shell style mask such as `*.(rs|toml)`,
+    /// to be completed by the caller
+    FileHint {
+        /// Only directory names should be offered
+        dirs_only: bool,
+        /// Mask for file names, or for directory names if `dirs_only` is set
+        mask: Option<String>,
+    },
+}
+
+/// Arguments typed on the command line before the word being completed
+///
+/// Passed to functions given to [`complete_with_context`](crate::Parser::complete_with_context)
+/// so values offered for one argument can depend on values already given to others. Names are
+/// written the way they appear on a command line: `"--repo"` or `"-r"`.
+#[derive(Debug, Clone)]
+pub struct CompletionContext {
+    items: Rc<[Arg]>,
+}
+
+impl CompletionContext {
+    pub(crate) fn new(args: &State) -> Self {
+        Self {
+            items: args.items.clone(),
+        }
+    }
+
+    /// Items before the word under cursor
+    fn typed(&self) -> &[Arg] {
+        &self.items[..self.items.len().saturating_sub(1)]
+    }
+
+    fn is_name(arg: &Arg, name: &str) -> bool {
+        match arg {
+            Arg::Short(c, _, _) => name
+                .strip_prefix('-')
+                .map_or(false, |n| n.starts_with(*c) && n.len() == c.len_utf8()),
+            Arg::Long(l, _, _) => name.strip_prefix("--") == Some(l.as_str()),
+            Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => false,
+        }
+    }
+
+    /// All values given to a named argument, in order they appear on the command line
+    ///
+    /// Values that are not valid utf8 are skipped
+    #[must_use]
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.typed()
+            .windows(2)
+            .filter(|w| Self::is_name(&w[0], name))
+            .filter_map(|w| match &w[1] {
+                Arg::ArgWord(v) | Arg::Word(v) => v.to_str(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Last value given to a named argument
+    #[must_use]
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values(name).pop()
+    }
+
+    /// Check if a flag or an argument is present on the command line
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.typed().iter().any(|arg| Self::is_name(arg, name))
+    }
+}
+
+/// Output revision used by [`completions`](crate::OptionParser::completions), candidates are
+/// passed as lines of escaped tab separated fields
+pub(crate) const IN_PROCESS_REV: usize = usize::MAX;
+
+pub(crate) fn escape(res: &mut String, field: &str) {
+    res.push('\t');
+    for c in field.chars() {
+        match c {
+            '\\' => res.push_str("\\\\"),
+            '\t' => res.push_str("\\t"),
+            '\n' => res.push_str("\\n"),
+            c => res.push(c),
+        }
+    }
+}
+
+pub(crate) fn unescape(field: &str) -> String {
+    let mut res = String::new();
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => res.push('\t'),
+                Some('n') => res.push('\n'),
+                Some(c) => res.push(c),
+                None => {}
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+pub(crate) fn render_in_process(items: &[ShowComp], ops: &[ShellComp]) -> String {
+    let mut res = String::new();
+    for item in items {
+        res.push_str(match item.kind {
+            ShowKind::Flag => "flag",
+            ShowKind::Command => "command",
+            ShowKind::Value => "value",
+        });
+        escape(&mut res, &item.subst);
+        escape(&mut res, &item.pretty);
+        escape(&mut res, item.extra.help.as_deref().unwrap_or(""));
+        escape(&mut res, item.extra.group.as_deref().unwrap_or(""));
+        res.push('\n');
+    }
+    for op in ops {
+        let (kind, mask) = match op {
+            ShellComp::File { mask } => ("file", mask),
+            ShellComp::Dir { mask } => ("dir", mask),
+            ShellComp::Raw { .. } | ShellComp::Nothing => continue,
+        };
+        res.push_str(kind);
+        escape(&mut res, mask.unwrap_or(""));
+        res.push('\n');
+    }
+    res
+}
+
+impl Completion {
+    /// Parse a line produced by [`render_in_process`]
+    pub(crate) fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t').map(unescape);
+        let mut next = || fields.next().unwrap_or_default();
+        let non_empty = |s: String| if s.is_empty() { None } else { Some(s) };
+        let kind = next();
+        let (kind, text) = match kind.as_str() {
+            "flag" => (CompletionKind::Flag, next()),
+            "command" => (CompletionKind::Command, next()),
+            "value" => (CompletionKind::Value, next()),
+            "file" | "dir" => {
+                let kind = CompletionKind::FileHint {
+                    dirs_only: kind == "dir",
+                    mask: non_empty(next()),
+                };
+                (kind, String::new())
+            }
+            _ => return None,
+        };
+        Some(Self {
+            text,
+            display: next(),
+            description: non_empty(next()),
+            group: non_empty(next()),
+            kind,
+        })
+    }
+}
 
 #[derive(Clone, Debug)]
 pub(crate) struct Complete {
@@ -29,6 +233,9 @@ pub(crate) struct Complete {
     comps: Vec<Comp>,
     pub(crate) output_rev: usize,
 
+    /// how to match names against partially typed text
+    pub(crate) matcher: CompletionMatcher,
+
     /// don't try to suggest any more positional items after there's a positional item failure
     /// or parsing in progress
     pub(crate) no_pos_ahead: bool,
@@ -39,6 +246,7 @@ impl Complete {
         Self {
             comps: Vec::new(),
             output_rev,
+            matcher: CompletionMatcher::Prefix,
             no_pos_ahead: false,
         }
     }
@@ -102,26 +310,100 @@ impl State {
                 extra,
                 meta,
                 is_argument,
+                hint: TypeHint::Any,
+            });
+        }
+    }
+
+    /// Run `parse` and attach a default completion hint to metavariables it adds
+    ///
+    /// `complete` and `complete_shell` replace metavariables, so hints apply only if neither
+    /// is used
+    pub(crate) fn with_hint<R, F>(&mut self, hint: Option<TypeHint>, parse: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let hint = match hint {
+            Some(hint) if self.is_comp() => hint,
+            _ => return parse(self),
+        };
+        let mut comps = Vec::new();
+        self.swap_comps_with(&mut comps);
+        let res = parse(self);
+        self.swap_comps_with(&mut comps);
+        if let Some(comp) = self.comp_mut() {
+            for mut item in comps {
+                if let Comp::Metavariable { hint: h, .. } = &mut item {
+                    *h = hint;
+                }
+                comp.push_comp(item);
+            }
+        }
+        res
+    }
+
+    /// Offer a value argument takes when user doesn't give one, `None` hides the value
+    pub(crate) fn push_current(&mut self, body: Option<String>, help: String, is_argument: bool) {
+        let depth = self.depth();
+        if let Some(comp) = self.comp_mut() {
+            comp.comps.push(Comp::Current {
+                extra: CompExtra {
+                    depth,
+                    group: None,
+                    help: Some(help),
+                },
+                body,
+                is_argument,
             });
         }
     }
 
+    /// Run `parse` and offer `body` as the current value if `parse` is completing a value
+    ///
+    /// Value from an environment variable added by `parse` takes priority
+    pub(crate) fn with_current<R, F>(&mut self, body: &str, parse: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        if self.comp_ref().is_none() {
+            return parse(self);
+        }
+        let mut comps = Vec::new();
+        self.swap_comps_with(&mut comps);
+        let res = parse(self);
+        self.swap_comps_with(&mut comps);
+        let is_argument = comps.iter().find_map(Comp::is_metavar);
+        let has_current = comps.iter().any(|c| matches!(c, Comp::Current { .. }));
+        if let Some(comp) = self.comp_mut() {
+            comp.extend_comps(comps);
+        }
+        if let (Some(is_argument), false) = (is_argument, has_current) {
+            self.push_current(
+                Some(body.to_owned()),
+                "current: default".to_owned(),
+                is_argument,
+            );
+        }
+        res
+    }
+
     /// Add a new completion hint for command, if needed
     pub(crate) fn push_command(
         &mut self,
-        name: &'static str,
+        name: &str,
         short: Option<char>,
         help: &Option<Doc>,
+        group: Option<&'static str>,
     ) {
         let depth = self.depth();
         if let Some(comp) = self.comp_mut() {
             comp.comps.push(Comp::Command {
                 extra: CompExtra {
                     depth,
-                    group: None,
+                    group: group.map(str::to_owned),
                     help: help.as_ref().and_then(Doc::to_completion),
                 },
-                name,
+                name: name.to_owned(),
                 short,
             });
         }
@@ -237,7 +519,7 @@ pub(crate) enum Comp {
     ///
     Command {
         extra: CompExtra,
-        name: &'static str,
+        name: String,
         short: Option<char>,
     },
 
@@ -251,11 +533,22 @@ pub(crate) enum Comp {
         is_argument: bool,
     },
 
+    /// value argument takes when user doesn't give one: from an environment variable or a
+    /// fallback, offered before everything else. `None` for environment variables that are
+    /// not offered, see [`complete_env_value`](crate::NamedArg::complete_env_value)
+    Current {
+        extra: CompExtra,
+        body: Option<String>,
+        is_argument: bool,
+    },
+
     Metavariable {
         extra: CompExtra,
         meta: &'static str,
         /// AKA not positional
         is_argument: bool,
+        /// Default completion derived from the value type
+        hint: TypeHint,
     },
 
     Shell {
@@ -272,6 +565,7 @@ impl Comp {
         match self {
             Comp::Command { extra, .. }
             | Comp::Value { extra, .. }
+            | Comp::Current { extra, .. }
             | Comp::Flag { extra, .. }
             | Comp::Shell { extra, .. }
             | Comp::Metavariable { extra, .. }
@@ -290,12 +584,27 @@ impl Comp {
         }
     }
 
+    fn current(&self) -> Option<&String> {
+        match self {
+            Comp::Current { body, .. } => body.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Drop the default hint, used when metavariable is replaced with user provided values
+    pub(crate) fn clear_hint(&mut self) {
+        if let Comp::Metavariable { hint, .. } = self {
+            *hint = TypeHint::Any;
+        }
+    }
+
     pub(crate) fn set_group(&mut self, group: String) {
         let extra = match self {
             Comp::Flag { extra, .. }
             | Comp::Argument { extra, .. }
             | Comp::Command { extra, .. }
             | Comp::Value { extra, .. }
+            | Comp::Current { extra, .. }
             | Comp::Shell { extra, .. }
             | Comp::Metavariable { extra, .. } => extra,
         };
@@ -305,6 +614,15 @@ impl Comp {
     }
 }
 
+/// What completion item inserts
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ShowKind {
+    Flag,
+    Command,
+    /// a value for a named argument or a positional item, including metavariables
+    Value,
+}
+
 #[derive(Debug)]
 pub(crate) struct ShowComp<'a> {
     /// value to be actually inserted by the autocomplete system
@@ -313,7 +631,12 @@ pub(crate) struct ShowComp<'a> {
     /// pretty rendering which might include metavars, etc
     pub(crate) pretty: String,
 
-    pub(crate) extra: &'a CompExtra,
+    pub(crate) extra: Cow<'a, CompExtra>,
+
+    /// match quality, lower is better, used by fuzzy matcher for ordering
+    pub(crate) score: usize,
+
+    pub(crate) kind: ShowKind,
 }
 
 impl std::fmt::Display for ShowComp<'_> {
@@ -349,16 +672,6 @@ fn pair_to_os_string<'a>(pair: (&'a Arg, &'a OsStr)) -> Option<(&'a Arg, &'a str
     Some((pair.0, pair.1.to_str()?))
 }
 
-/// What is the preceeding item, if any
-///
-/// Mostly is there to tell if we are trying to complete and argument or not...
-#[derive(Debug, Copy, Clone)]
-enum Prefix<'a> {
-    NA,
-    Short(char),
-    Long(&'a str),
-}
-
 impl State {
     /// Generate completion from collected heads
     ///
@@ -384,13 +697,15 @@ impl State {
         // last value is going  to be either Arg::Word or Arg::ArgWord
         // so to perform full completion we look at the preceeding item
         // and use it's value if it was a composite short/long argument
+        //
+        // prefix is the part of the word before the value, completed values must keep it as typed
         let preceeding = items.next();
-        let (pos_only, full_lit) = match preceeding {
+        let (pos_only, full_lit, prefix) = match preceeding {
             Some((Arg::Short(_, true, _os) | Arg::Long(_, true, _os), full_lit)) => {
-                (false, full_lit)
+                (false, full_lit, full_lit.strip_suffix(lit).unwrap_or(""))
             }
-            Some((Arg::PosWord(_), _)) => (true, lit),
-            _ => (false, lit),
+            Some((Arg::PosWord(_), _)) => (true, lit, ""),
+            _ => (false, lit, ""),
         };
 
         let is_named = match cur {
@@ -398,20 +713,23 @@ impl State {
             Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => false,
         };
 
-        let prefix = match preceeding {
-            Some((Arg::Short(s, true, _os), _lit)) => Prefix::Short(*s),
-            Some((Arg::Long(l, true, _os), _lit)) => Prefix::Long(l.as_str()),
-            _ => Prefix::NA,
-        };
-
         let (items, shell) = comp.complete(lit, pos_only, is_named, prefix);
 
         Some(match comp.output_rev {
             0 => render_test(&items, &shell, full_lit),
             1 => render_simple(&items), // <- AKA elvish
-            7 => render_zsh(&items, &shell, full_lit),
-            8 => render_bash(&items, &shell, full_lit),
+            7 => render_zsh(
+                &items,
+                &shell,
+                full_lit,
+                prefix,
+                comp.matcher == CompletionMatcher::Fuzzy,
+            ),
+            8 => render_bash(&items, &shell, full_lit, prefix),
             9 => render_fish(&items, &shell, full_lit, self.path[0].as_str()),
+            10 => render_powershell(&items, &shell, full_lit, prefix),
+            11 => render_nushell(&items, &shell, full_lit, prefix),
+            IN_PROCESS_REV => Ok(render_in_process(&items, &shell)),
             unk => {
                 #[cfg(debug_assertions)]
                 {
@@ -435,6 +753,100 @@ fn preferred_name(name: ShortLong) -> String {
     }
 }
 
+/// How well typed text matches a name, lower is better
+///
+/// Exact prefix, case insensitive prefix, substring and finally a subsequence with fewer
+/// skipped characters
+fn fuzzy_score(input: &str, name: &str) -> Option<usize> {
+    if name.starts_with(input) {
+        return Some(0);
+    }
+    let input = input.to_lowercase();
+    let name = name.to_lowercase();
+    if name.starts_with(&input) {
+        Some(1)
+    } else if name.contains(&input) {
+        Some(2)
+    } else {
+        let mut gaps = 0;
+        let mut chars = name.chars();
+        for c in input.chars() {
+            loop {
+                match chars.next() {
+                    Some(n) if n == c => break,
+                    Some(_) => gaps += 1,
+                    None => return None,
+                }
+            }
+        }
+        Some(3 + gaps)
+    }
+}
+
+/// Score for names with small typos, used when nothing else matches
+fn typo_score(input: &str, name: &str) -> Option<usize> {
+    let distance = damerau_levenshtein(&input.to_lowercase(), &name.to_lowercase());
+    if distance <= 2 {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+impl CompletionMatcher {
+    /// Check if argument can possibly match a flag or an argument name
+    ///
+    /// Returns a preferrable replacement and match score
+    fn arg(self, arg: &str, name: ShortLong, typo: bool) -> Option<(String, usize)> {
+        if self == CompletionMatcher::Prefix || arg.is_empty() || arg == "-" {
+            return if typo {
+                None
+            } else {
+                arg_matches(arg, name).map(|n| (n, 0))
+            };
+        }
+        // exact short name
+        if let Some(s) = name.as_short() {
+            if !typo
+                && arg
+                    .strip_prefix('-')
+                    .and_then(|a| a.strip_prefix(s))
+                    .map_or(false, str::is_empty)
+            {
+                return Some((preferred_name(name), 0));
+            }
+        }
+        let long = name.as_long()?;
+        let typed = arg.strip_prefix("--")?;
+        let score = if typo {
+            typo_score(typed, long)?
+        } else {
+            fuzzy_score(typed, long)?
+        };
+        Some((preferred_name(name), score))
+    }
+
+    /// Check if argument can possibly match a command name
+    fn cmd<'a>(
+        self,
+        arg: &str,
+        name: &'a str,
+        short: Option<char>,
+        typo: bool,
+    ) -> Option<(&'a str, usize)> {
+        if typo {
+            return Some((name, typo_score(arg, name)?));
+        }
+        if let Some(name) = cmd_matches(arg, name, short) {
+            return Some((name, 0));
+        }
+        match self {
+            CompletionMatcher::Prefix => None,
+            CompletionMatcher::Fuzzy => Some((name, fuzzy_score(arg, name)?)),
+        }
+    }
+}
+
 // check if argument can possibly match the argument passed in and returns a preferrable replacement
 fn arg_matches(arg: &str, name: ShortLong) -> Option<String> {
     // "" and "-" match any flag
@@ -470,7 +882,7 @@ fn arg_matches(arg: &str, name: ShortLong) -> Option<String> {
         None
     }
 }
-fn cmd_matches(arg: &str, name: &'static str, short: Option<char>) -> Option<&'static str> {
+fn cmd_matches<'a>(arg: &str, name: &'a str, short: Option<char>) -> Option<&'a str> {
     // partial long name and exact short name match anything
     if name.starts_with(arg)
         || short.map_or(false, |s| {
@@ -491,30 +903,78 @@ impl Comp {
             Comp::Flag { .. } | Comp::Argument { .. } | Comp::Command { .. } => false,
             Comp::Metavariable { is_argument, .. }
             | Comp::Value { is_argument, .. }
+            | Comp::Current { is_argument, .. }
             | Comp::Shell { is_argument, .. } => *is_argument,
         }
     }
     fn is_pos(&self) -> bool {
         match self {
             Comp::Flag { .. } | Comp::Argument { .. } | Comp::Command { .. } => false,
-            Comp::Value { is_argument, .. } => !is_argument,
+            Comp::Value { is_argument, .. } | Comp::Current { is_argument, .. } => !is_argument,
             Comp::Metavariable { .. } | Comp::Shell { .. } => true,
         }
     }
 }
 
 impl Complete {
+    /// Try to match a flag, an argument or a command name, `None` for other items
+    fn complete_name<'a>(&self, item: &'a Comp, arg: &str, typo: bool) -> Option<ShowComp<'a>> {
+        match item {
+            Comp::Command { name, short, extra } => {
+                let (long, score) = self.matcher.cmd(arg, name, *short, typo)?;
+                Some(ShowComp {
+                    subst: long.to_string(),
+                    pretty: long.to_string(),
+                    extra: Cow::Borrowed(extra),
+                    score,
+                    kind: ShowKind::Command,
+                })
+            }
+
+            Comp::Flag { name, extra } => {
+                let (long, score) = self.matcher.arg(arg, *name, typo)?;
+                Some(ShowComp {
+                    pretty: long.clone(),
+                    subst: long,
+                    extra: Cow::Borrowed(extra),
+                    score,
+                    kind: ShowKind::Flag,
+                })
+            }
+
+            Comp::Argument {
+                name,
+                metavar,
+                extra,
+            } => {
+                let (long, score) = self.matcher.arg(arg, *name, typo)?;
+                Some(ShowComp {
+                    pretty: format!("{}={}", long, metavar),
+                    subst: long,
+                    extra: Cow::Borrowed(extra),
+                    score,
+                    kind: ShowKind::Flag,
+                })
+            }
+            Comp::Value { .. }
+            | Comp::Current { .. }
+            | Comp::Metavariable { .. }
+            | Comp::Shell { .. } => None,
+        }
+    }
+
     fn complete(
         &self,
         arg: &str,
         pos_only: bool,
         is_named: bool,
-        prefix: Prefix,
+        prefix: &str,
     ) -> (Vec<ShowComp>, Vec<ShellComp>) {
         let mut items: Vec<ShowComp> = Vec::new();
         let mut shell = Vec::new();
         let max_depth = self.comps.iter().map(Comp::depth).max().unwrap_or(0);
         let mut only_values = false;
+        let mut names_matched = false;
 
         for item in self
             .comps
@@ -531,37 +991,10 @@ impl Complete {
             }
 
             match item {
-                Comp::Command { name, short, extra } => {
-                    if let Some(long) = cmd_matches(arg, name, *short) {
-                        items.push(ShowComp {
-                            subst: long.to_string(),
-                            pretty: long.to_string(),
-                            extra,
-                        });
-                    }
-                }
-
-                Comp::Flag { name, extra } => {
-                    if let Some(long) = arg_matches(arg, *name) {
-                        items.push(ShowComp {
-                            pretty: long.clone(),
-                            subst: long,
-                            extra,
-                        });
-                    }
-                }
-
-                Comp::Argument {
-                    name,
-                    metavar,
-                    extra,
-                } => {
-                    if let Some(long) = arg_matches(arg, *name) {
-                        items.push(ShowComp {
-                            pretty: format!("{}={}", long, metavar),
-                            subst: long,
-                            extra,
-                        });
+                Comp::Command { .. } | Comp::Flag { .. } | Comp::Argument { .. } => {
+                    if let Some(show) = self.complete_name(item, arg, false) {
+                        names_matched = true;
+                        items.push(show);
                     }
                 }
 
@@ -570,30 +1003,91 @@ impl Complete {
                     extra,
                     is_argument: _,
                 } => {
+                    // already offered as the current value
+                    if self.comps.iter().any(|c| c.current() == Some(body)) {
+                        continue;
+                    }
                     items.push(ShowComp {
                         pretty: body.clone(),
-                        extra,
-                        subst: match prefix {
-                            Prefix::NA => body.clone(),
-                            Prefix::Short(s) => format!("-{}={}", s, body),
-                            Prefix::Long(l) => format!("--{}={}", l, body),
-                        },
+                        extra: Cow::Borrowed(extra),
+                        score: 0,
+                        subst: format!("{}{}", prefix, body),
+                        kind: ShowKind::Value,
                     });
                 }
 
+                Comp::Current { body: None, .. } => {}
+
+                Comp::Current {
+                    body: Some(body),
+                    extra,
+                    ..
+                } => {
+                    if !body.starts_with(arg) {
+                        continue;
+                    }
+                    // fuzzy matcher sort is stable and keeps it in front
+                    items.insert(
+                        0,
+                        ShowComp {
+                            pretty: body.clone(),
+                            extra: Cow::Borrowed(extra),
+                            score: 0,
+                            subst: format!("{}{}", prefix, body),
+                            kind: ShowKind::Value,
+                        },
+                    );
+                }
+
                 Comp::Metavariable {
                     extra,
                     meta,
                     is_argument,
+                    hint,
                 } => {
                     if !is_argument && !pos_only && arg.starts_with('-') {
                         continue;
                     }
-                    items.push(ShowComp {
-                        subst: String::new(),
-                        pretty: (*meta).to_string(),
-                        extra,
-                    });
+                    let values = match hint {
+                        TypeHint::File | TypeHint::Dir => {
+                            if !is_named {
+                                shell.push(if *hint == TypeHint::File {
+                                    ShellComp::File { mask: None }
+                                } else {
+                                    ShellComp::Dir { mask: None }
+                                });
+                            }
+                            continue;
+                        }
+                        TypeHint::OneOf(values) => values
+                            .iter()
+                            .filter(|(value, _)| value.starts_with(arg))
+                            .collect::<Vec<_>>(),
+                        TypeHint::Any => Vec::new(),
+                    };
+                    // same as with `complete` a single good suggestion replaces the metavar
+                    if values.len() != 1 {
+                        items.push(ShowComp {
+                            subst: String::new(),
+                            pretty: (*meta).to_string(),
+                            extra: Cow::Borrowed(extra),
+                            score: 0,
+                            kind: ShowKind::Value,
+                        });
+                    }
+                    for (value, help) in values {
+                        items.push(ShowComp {
+                            subst: format!("{}{}", prefix, value),
+                            pretty: (*value).to_string(),
+                            extra: Cow::Owned(CompExtra {
+                                depth: extra.depth,
+                                group: extra.group.clone(),
+                                help: help.map(str::to_owned),
+                            }),
+                            score: 0,
+                            kind: ShowKind::Value,
+                        });
+                    }
                 }
 
                 Comp::Shell { script, .. } => {
@@ -604,6 +1098,19 @@ impl Complete {
             }
         }
 
+        if self.matcher == CompletionMatcher::Fuzzy {
+            // nothing matches what user typed, maybe there's a typo
+            if !(only_values || names_matched || arg.is_empty() || arg == "-") {
+                items.extend(
+                    self.comps
+                        .iter()
+                        .filter(|c| c.depth() == max_depth && !pos_only)
+                        .filter_map(|c| self.complete_name(c, arg, true)),
+                );
+            }
+            items.sort_by_key(|i| i.score);
+        }
+
         (items, shell)
     }
 }
@@ -0,0 +1,366 @@
+//! Installing completion scripts into per user locations
+//!
+//! `--bpaf-complete-install=SHELL` writes the dynamic completion script where the shell looks
+//! for it and `--bpaf-complete-uninstall=SHELL` removes it again:
+//!
+//! - bash: `$BASH_COMPLETION_USER_DIR/completions/NAME`, or
+//!   `$XDG_DATA_HOME/bash-completion/completions/NAME`
+//! - zsh: `$XDG_DATA_HOME/zsh/site-functions/_NAME`, user needs to add it to `fpath`
+//! - fish: `$XDG_CONFIG_HOME/fish/completions/NAME.fish`
+//! - elvish: `$XDG_CONFIG_HOME/elvish/rc.elv`, between marker lines
+//!
+//! Scripts carry a marker line and only files with this marker are ever removed or replaced.
+
+use crate::complete_run::{completer, Protocol};
+use std::path::{Path, PathBuf};
+
+/// Directories completion scripts go to, derived from the environment
+#[derive(Debug)]
+pub(crate) struct Dirs {
+    home: PathBuf,
+    /// `$XDG_DATA_HOME` or `~/.local/share`
+    data: PathBuf,
+    /// `$XDG_CONFIG_HOME` or `~/.config`
+    config: PathBuf,
+    /// `$BASH_COMPLETION_USER_DIR`
+    bash: Option<PathBuf>,
+    /// Entries from `$FPATH`
+    fpath: Vec<PathBuf>,
+}
+
+impl Dirs {
+    pub(crate) fn from_env<F>(var: F) -> Option<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        // relative XDG paths are invalid and should be ignored
+        let absolute = |name: &str| var(name).map(PathBuf::from).filter(|p| p.is_absolute());
+        let home = PathBuf::from(var("HOME")?);
+        let fpath = var("FPATH")
+            .unwrap_or_default()
+            .split(':')
+            .map(PathBuf::from)
+            .collect();
+        Some(Self {
+            data: absolute("XDG_DATA_HOME").unwrap_or_else(|| home.join(".local/share")),
+            config: absolute("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config")),
+            bash: absolute("BASH_COMPLETION_USER_DIR"),
+            fpath,
+            home,
+        })
+    }
+
+    /// File the script for a given shell is installed to
+    fn target(&self, shell: &str, name: &str) -> Option<PathBuf> {
+        Some(match shell {
+            "bash" => {
+                let dir = self
+                    .bash
+                    .clone()
+                    .unwrap_or_else(|| self.data.join("bash-completion"));
+                dir.join("completions").join(name)
+            }
+            "zsh" => self
+                .data
+                .join("zsh/site-functions")
+                .join(format!("_{}", name)),
+            "fish" => self
+                .config
+                .join("fish/completions")
+                .join(format!("{}.fish", name)),
+            "elvish" => self.config.join("elvish/rc.elv"),
+            _ => return None,
+        })
+    }
+}
+
+/// Last line of every script file written by [`install`]
+fn marker(name: &str) -> String {
+    format!("# {} completion, generated by bpaf", name)
+}
+
+/// Script file exists and was written by [`install`]
+fn has_marker(path: &Path, name: &str) -> bool {
+    let marker = marker(name);
+    std::fs::read_to_string(path).map_or(false, |s| s.lines().any(|l| l == marker))
+}
+
+/// Elvish has no completion directory, script goes into `rc.elv` between these markers
+fn elvish_markers(name: &str) -> (String, String) {
+    (
+        format!("# >>> {} completion, generated by bpaf", name),
+        format!("# <<< {} completion", name),
+    )
+}
+
+/// Remove elvish script from `rc`, `None` if there was nothing to remove
+fn strip_elvish(rc: &str, name: &str) -> Option<String> {
+    let (start, end) = elvish_markers(name);
+    let from = rc.find(&start)?;
+    let to = from + rc[from..].find(&end)? + end.len();
+    let to = if rc[to..].starts_with('\n') {
+        to + 1
+    } else {
+        to
+    };
+    Some(format!("{}{}", &rc[..from], &rc[to..]))
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> String {
+    format!("Can't write to {}: {}", path.display(), err)
+}
+
+/// Install completion script for the app, returns a report for the user
+pub(crate) fn install(
+    shell: &str,
+    name: &str,
+    protocol: Protocol,
+    dirs: &Dirs,
+) -> Result<String, String> {
+    let (script, path) = completer(shell, name, protocol)
+        .zip(dirs.target(shell, name))
+        .ok_or_else(|| {
+            format!(
+                "Can't install completion for {}, supported shells are bash, zsh, fish and elvish",
+                shell
+            )
+        })?;
+    if shell != "elvish" && path.exists() && !has_marker(&path, name) {
+        return Err(format!(
+            "Can't install completion for {} to {}, file exists and was not generated by bpaf",
+            name,
+            path.display()
+        ));
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+    }
+
+    let mut report = format!(
+        "Installed {} completion for {} to {}\n",
+        shell,
+        name,
+        path.display()
+    );
+    if shell == "elvish" {
+        let rc = std::fs::read_to_string(&path).unwrap_or_default();
+        let mut rc = strip_elvish(&rc, name).unwrap_or(rc);
+        if !(rc.is_empty() || rc.ends_with('\n')) {
+            rc.push('\n');
+        }
+        let (start, end) = elvish_markers(name);
+        rc.push_str(&format!("{}\n{}\n{}\n", start, script, end));
+        std::fs::write(&path, rc).map_err(|e| io_error(&path, e))?;
+    } else {
+        let script = format!("{}\n{}\n", script, marker(name));
+        std::fs::write(&path, script).map_err(|e| io_error(&path, e))?;
+    }
+
+    if shell == "zsh" && !dirs.fpath.iter().any(|dir| path.parent() == Some(dir)) {
+        let dir = path.parent().unwrap_or(&path);
+        report.push_str(&format!(
+            "Add this line to {} before compinit is called:\n    fpath+=({})\n",
+            dirs.home.join(".zshrc").display(),
+            dir.display()
+        ));
+    }
+    report.push_str("Restart your shell to enable it\n");
+    Ok(report)
+}
+
+/// Remove completion script installed with [`install`], returns a report for the user
+pub(crate) fn uninstall(shell: &str, name: &str, dirs: &Dirs) -> Result<String, String> {
+    let path = dirs.target(shell, name).ok_or_else(|| {
+        format!(
+            "Can't uninstall completion for {}, supported shells are bash, zsh, fish and elvish",
+            shell
+        )
+    })?;
+    if shell == "elvish" {
+        let rc = std::fs::read_to_string(&path).unwrap_or_default();
+        match strip_elvish(&rc, name) {
+            Some(rc) => std::fs::write(&path, rc).map_err(|e| io_error(&path, e))?,
+            None => {
+                return Ok(format!(
+                    "{} completion for {} is not installed\n",
+                    shell, name
+                ))
+            }
+        }
+    } else if has_marker(&path, name) {
+        std::fs::remove_file(&path).map_err(|e| io_error(&path, e))?;
+    } else if path.exists() {
+        return Err(format!(
+            "Can't uninstall completion for {} from {}, file was not generated by bpaf",
+            name,
+            path.display()
+        ));
+    } else {
+        return Ok(format!(
+            "{} completion for {} is not installed\n",
+            shell, name
+        ));
+    }
+    Ok(format!(
+        "Removed {} completion for {} from {}\n",
+        shell,
+        name,
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{install, uninstall, Dirs};
+    use crate::complete_run::Protocol;
+    use std::path::Path;
+
+    fn dirs(home: &Path, xdg_data: Option<&str>, fpath: Option<&str>) -> Dirs {
+        Dirs::from_env(|var| match var {
+            "HOME" => Some(home.display().to_string()),
+            "XDG_DATA_HOME" => xdg_data.map(|d| home.join(d).display().to_string()),
+            "FPATH" => fpath.map(|f| {
+                f.split(':')
+                    .map(|d| home.join(d).display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(":")
+            }),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    fn temp_home(test: &str) -> std::path::PathBuf {
+        let home = std::env::temp_dir().join(format!("bpaf_{}_{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(&home).unwrap();
+        home
+    }
+
+    #[test]
+    fn install_bash_and_fish() {
+        let home = temp_home("install_bash");
+        let dirs = dirs(&home, Some("data"), None);
+
+        let bash = home.join("data/bash-completion/completions/app");
+        let r = install("bash", "app", Protocol::Flag, &dirs).unwrap();
+        assert_eq!(
+            r,
+            format!(
+                "Installed bash completion for app to {}\nRestart your shell to enable it\n",
+                bash.display()
+            )
+        );
+        let script = std::fs::read_to_string(&bash).unwrap();
+        assert!(script.ends_with(
+            "complete -o nosort -F _bpaf_dynamic_completion app\n# app completion, generated by bpaf\n"
+        ));
+
+        let fish = home.join(".config/fish/completions/app.fish");
+        install("fish", "app", Protocol::Flag, &dirs).unwrap();
+        assert!(fish.is_file());
+
+        let r = uninstall("bash", "app", &dirs).unwrap();
+        assert_eq!(
+            r,
+            format!("Removed bash completion for app from {}\n", bash.display())
+        );
+        assert!(!bash.exists());
+        let r = uninstall("bash", "app", &dirs).unwrap();
+        assert_eq!(r, "bash completion for app is not installed\n");
+
+        assert!(install("cmd", "app", Protocol::Flag, &dirs).is_err());
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn install_zsh() {
+        let home = temp_home("install_zsh");
+
+        // user needs to add the directory to fpath unless it's there already
+        let dirs = dirs(&home, None, None);
+        let file = home.join(".local/share/zsh/site-functions/_app");
+        let r = install("zsh", "app", Protocol::Flag, &dirs).unwrap();
+        assert!(r.contains(&format!(
+            "    fpath+=({})\n",
+            file.parent().unwrap().display()
+        )));
+        let script = std::fs::read_to_string(&file).unwrap();
+        assert!(script.starts_with("#compdef app\n"));
+        assert!(script.ends_with("\n# app completion, generated by bpaf\n"));
+
+        let dirs = self::dirs(&home, None, Some(".zfunc:.local/share/zsh/site-functions"));
+        let r = install("zsh", "app", Protocol::Flag, &dirs).unwrap();
+        assert!(!r.contains("fpath+="));
+
+        // files from other places in fpath are left alone
+        let other = home.join(".zfunc/_app");
+        std::fs::create_dir_all(other.parent().unwrap()).unwrap();
+        std::fs::write(&other, "#compdef app\n").unwrap();
+        let r = uninstall("zsh", "app", &dirs).unwrap();
+        assert_eq!(r.lines().count(), 1);
+        assert!(!file.exists());
+        assert!(other.exists());
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn foreign_files_are_kept() {
+        let home = temp_home("foreign_files");
+        let dirs = dirs(&home, None, None);
+        let file = home.join(".config/fish/completions/app.fish");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "complete -c app -a hand-written\n").unwrap();
+
+        let err = install("fish", "app", Protocol::Flag, &dirs).unwrap_err();
+        assert!(err.ends_with("file exists and was not generated by bpaf"));
+        let err = uninstall("fish", "app", &dirs).unwrap_err();
+        assert!(err.ends_with("file was not generated by bpaf"));
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "complete -c app -a hand-written\n"
+        );
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn install_env_protocol() {
+        let home = temp_home("install_env");
+        let dirs = dirs(&home, Some("data"), None);
+
+        let protocol = Protocol::Env("APP_COMPLETE");
+        install("bash", "app", protocol, &dirs).unwrap();
+        let script =
+            std::fs::read_to_string(home.join("data/bash-completion/completions/app")).unwrap();
+        assert!(script.contains("APP_COMPLETE=bash $1"));
+        assert!(!script.contains("--bpaf-complete-rev"));
+
+        install("fish", "app", protocol, &dirs).unwrap();
+        let script =
+            std::fs::read_to_string(home.join(".config/fish/completions/app.fish")).unwrap();
+        assert!(script.contains("env APP_COMPLETE=fish $current"));
+        assert!(!script.contains("--bpaf-complete-rev"));
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn install_elvish() {
+        let home = temp_home("install_elvish");
+        let dirs = dirs(&home, None, None);
+        let rc = home.join(".config/elvish/rc.elv");
+        std::fs::create_dir_all(rc.parent().unwrap()).unwrap();
+        std::fs::write(&rc, "use str").unwrap();
+
+        // installing twice replaces the script
+        install("elvish", "app", Protocol::Flag, &dirs).unwrap();
+        install("elvish", "app", Protocol::Flag, &dirs).unwrap();
+        let contents = std::fs::read_to_string(&rc).unwrap();
+        assert!(contents.starts_with("use str\n# >>> app completion, generated by bpaf\n"));
+        assert!(contents.ends_with("}\n# <<< app completion\n"));
+        assert_eq!(contents.matches("arg-completer[app]").count(), 1);
+
+        uninstall("elvish", "app", &dirs).unwrap();
+        assert_eq!(std::fs::read_to_string(&rc).unwrap(), "use str\n");
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+}
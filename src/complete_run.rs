@@ -1,55 +1,102 @@
-use crate::complete_gen::Complete;
+use crate::{
+    args::Args,
+    complete_gen::Complete,
+    complete_install::{install, uninstall, Dirs},
+    complete_shell::{Nushell, PowerShell},
+};
 use std::ffi::OsStr;
 
-fn dump_bash_completer(name: &str) {
-    println!(
+/// How completion scripts ask the app for completions
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Protocol<'a> {
+    /// `--bpaf-complete-rev=N` as the first argument
+    Flag,
+    /// Environment variable with a shell name, see
+    /// [`completion_env`](crate::OptionParser::completion_env)
+    Env(&'a str),
+}
+
+/// Shell name used by the environment variable protocol and its output revision
+const SHELLS: &[(&str, usize)] = &[
+    ("zsh", 7),
+    ("bash", 8),
+    ("fish", 9),
+    ("elvish", 1),
+    ("powershell", 10),
+    ("nushell", 11),
+];
+
+// bash splits "--key=val" into "--key", "=" and "val", they are glued back together
+fn bash_completer(name: &str, protocol: Protocol) -> String {
+    format!(
         r#"_bpaf_dynamic_completion()
 {{
-    line="$1 --bpaf-complete-rev=8 ${{COMP_WORDS[@]:1}}"
-    if [[ ${{COMP_WORDS[-1]}} == "" ]]; then
+    local word glue=""
+    line="{invoke}"
+    for word in "${{COMP_WORDS[@]:1}}"; do
+        if [[ $word == "=" || -n $glue ]]; then
+            line="${{line}}${{word}}"
+        else
+            line="${{line}} ${{word}}"
+        fi
+        [[ $word == "=" ]] && glue=1 || glue=""
+    done
+    if [[ ${{COMP_WORDS[-1]}} == "" && ${{COMP_WORDS[-2]}} != "=" ]]; then
         line="${{line}} \"\""
     fi
     source <( eval ${{line}})
 }}
 complete -o nosort -F _bpaf_dynamic_completion {name}"#,
         name = name,
-    );
+        invoke = match protocol {
+            Protocol::Flag => "$1 --bpaf-complete-rev=8".to_owned(),
+            Protocol::Env(var) => format!("{}=bash $1", var),
+        },
+    )
 }
 
-fn dump_zsh_completer(name: &str) {
-    println!(
+fn zsh_completer(name: &str, protocol: Protocol) -> String {
+    format!(
         r#"#compdef {name}
 local line
-line="${{words[1]}} --bpaf-complete-rev=7 ${{words[@]:1}}"
+line="{invoke} ${{words[@]:1}}"
 if [[ ${{words[-1]}} == "" ]]; then
     line="${{line}} \"\""
 fi
 source <(eval ${{line}})
 "#,
-        name = name
-    );
+        name = name,
+        invoke = match protocol {
+            Protocol::Flag => "${words[1]} --bpaf-complete-rev=7".to_owned(),
+            Protocol::Env(var) => format!("{}=zsh ${{words[1]}}", var),
+        },
+    )
 }
 
-fn dump_fish_completer(_name: &str) {
-    println!(
+fn fish_completer(protocol: Protocol) -> String {
+    format!(
         r#"set -l current (commandline --tokenize --current-process)
-set -l tmpline $current[1] --bpaf-complete-rev=9 $current[2..]
+set -l tmpline {invoke}
 if test (commandline --current-process) != (string trim (commandline --current-process))
     set tmpline $tmpline ""
 end
-source ( $tmpline | psub )"#
-    );
+source ( $tmpline | psub )"#,
+        invoke = match protocol {
+            Protocol::Flag => "$current[1] --bpaf-complete-rev=9 $current[2..]".to_owned(),
+            Protocol::Env(var) => format!("env {}=fish $current", var),
+        },
+    )
 }
 
 // I would love to support elvish better but debugger is not a thing
 // and on any error in code it simply replies "no candidates" with no
 // obvious way even to print "you are here"...
 // https://github.com/elves/elvish/issues/803
-fn dump_elvish_completer(name: &str) {
-    println!(
+fn elvish_completer(name: &str, protocol: Protocol) -> String {
+    format!(
         "\
 set edit:completion:arg-completer[{name}] = {{ |@args| var args = $args[1..];
-     var @lines = ( {name} --bpaf-complete-rev={rev} $@args );
+     var @lines = ( {invoke} $@args );
      use str;
      for line $lines {{
          var @arg = (str:split \"\\t\" $line)
@@ -61,37 +108,189 @@ set edit:completion:arg-completer[{name}] = {{ |@args| var args = $args[1..];
      }}
 }}",
         name = name,
-        rev = 1,
-    );
+        invoke = match protocol {
+            Protocol::Flag => format!("{} --bpaf-complete-rev={}", name, 1),
+            Protocol::Env(var) => format!("env {}=elvish {}", var, name),
+        },
+    )
+}
+
+// the app prints tab separated fields, see `render_powershell`, script turns them into
+// completion results without evaluating anything
+fn powershell_completer(name: &str, protocol: Protocol) -> String {
+    format!(
+        r#"Register-ArgumentCompleter -Native -CommandName {name} -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $words = @($commandAst.CommandElements |
+        Where-Object {{ $_.Extent.StartOffset -lt $cursorPosition }} |
+        ForEach-Object {{ $_.Extent.Text }})
+    if ($wordToComplete -eq '') {{
+        $words += ''
+    }}
+    $program, $rest = $words
+{invoke}
+    foreach ($line in $lines) {{
+        $kind, $fields = $line -split "`t"
+        if ($fields.Count -ne 3) {{
+            continue
+        }}
+        if ($kind -eq 'Files' -or $kind -eq 'Dirs') {{
+            $word, $mask, $prefix = $fields
+            [System.Management.Automation.CompletionCompleters]::CompleteFilename($word) |
+                Where-Object {{
+                    $dir = $_.ResultType -eq 'ProviderContainer'
+                    $match = $mask -eq '' -or $_.ListItemText -like $mask
+                    if ($kind -eq 'Files') {{ $dir -or $match }} else {{ $dir -and $match }}
+                }} |
+                ForEach-Object {{
+                    [System.Management.Automation.CompletionResult]::new(
+                        $prefix + $_.CompletionText, $_.ListItemText, $_.ResultType, $_.ToolTip)
+                }}
+        }} elseif ($kind -in 'ParameterValue', 'ParameterName', 'Text') {{
+            $text, $shown, $tooltip = $fields
+            if ($text -ne '') {{
+                [System.Management.Automation.CompletionResult]::new($text, $shown, $kind, $tooltip)
+            }}
+        }}
+    }}
+}}"#,
+        name = PowerShell(name),
+        invoke = match protocol {
+            Protocol::Flag => format!("    $lines = & $program --bpaf-complete-rev={} @rest", 10),
+            Protocol::Env(var) => format!(
+                "    $env:{var} = 'powershell'
+    $lines = & $program @rest
+    Remove-Item Env:{var}",
+                var = var
+            ),
+        },
+    )
+}
+
+// nushell has a single external completer for all the commands so the script
+// passes everything else to the completer that was set before, if any
+fn nushell_completer(name: &str, protocol: Protocol) -> String {
+    format!(
+        r#"let bpaf_{ident}_fallback = $env.config.completions.external.completer?
+$env.config.completions.external = {{
+    enable: true
+    completer: {{|spans|
+        if $spans.0 == {name} {{
+            {invoke} | from json
+        }} else if $bpaf_{ident}_fallback != null {{
+            do $bpaf_{ident}_fallback $spans
+        }}
+    }}
+}}"#,
+        name = Nushell(name),
+        ident = name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"),
+        invoke = match protocol {
+            Protocol::Flag => format!("^$spans.0 --bpaf-complete-rev={} ...($spans | skip 1)", 11),
+            Protocol::Env(var) => format!(
+                "with-env {{ {}: nushell }} {{ ^$spans.0 ...($spans | skip 1) }}",
+                var
+            ),
+        },
+    )
+}
+
+pub(crate) fn completer(shell: &str, name: &str, protocol: Protocol) -> Option<String> {
+    Some(match shell {
+        "zsh" => zsh_completer(name, protocol),
+        "bash" => bash_completer(name, protocol),
+        "fish" => fish_completer(protocol),
+        "elvish" => elvish_completer(name, protocol),
+        "powershell" => powershell_completer(name, protocol),
+        "nushell" => nushell_completer(name, protocol),
+        _ => return None,
+    })
+}
+
+/// Handle the environment variable protocol before parsing
+///
+/// With `var` set to a shell name and no arguments the app should print completion script,
+/// `Err` contains the script. With arguments they are words to complete, passed verbatim.
+pub(crate) fn env_protocol<'a>(var: &'static str, mut args: Args<'a>) -> Result<Args<'a>, String> {
+    args.c_env = Some(var);
+    let shell = match std::env::var(var) {
+        Ok(shell) if args.c_rev.is_none() => shell,
+        _ => return Ok(args),
+    };
+    let rev = match SHELLS.iter().find(|(name, _)| *name == shell) {
+        Some((_, rev)) => *rev,
+        None => return Ok(args),
+    };
+    if args.items.len() == 0 {
+        if let Some(script) = args
+            .name
+            .as_deref()
+            .and_then(|name| completer(&shell, name, Protocol::Env(var)))
+        {
+            return Err(format!("{}\n", script));
+        }
+    }
+    args.c_rev = Some(rev);
+    args.c_verbatim = true;
+    Ok(args)
 }
 
 #[derive(Debug)]
 pub(crate) struct ArgScanner<'a> {
     pub(crate) revision: Option<usize>,
     pub(crate) name: Option<&'a str>,
+    /// Arguments are passed by the environment variable protocol, don't look for
+    /// completion flags in them
+    pub(crate) verbatim: bool,
+    /// How the generated completion scripts should ask the app for completions
+    pub(crate) protocol: Protocol<'a>,
 }
 
 impl ArgScanner<'_> {
     pub(crate) fn check_next(&mut self, arg: &OsStr) -> bool {
+        if self.verbatim {
+            return false;
+        }
         let arg = match arg.to_str() {
             Some(arg) => arg,
             None => return false,
         };
         // this only works when there's a name
         if let Some(name) = &self.name {
-            let mut matched = true;
-            match arg {
-                "--bpaf-complete-style-zsh" => dump_zsh_completer(name),
-                "--bpaf-complete-style-bash" => dump_bash_completer(name),
-                "--bpaf-complete-style-fish" => dump_fish_completer(name),
-                "--bpaf-complete-style-elvish" => dump_elvish_completer(name),
-                _ => {
-                    matched = false;
-                }
-            }
-            if matched {
+            if let Some(script) = arg
+                .strip_prefix("--bpaf-complete-style-")
+                .and_then(|shell| completer(shell, name, self.protocol))
+            {
+                println!("{}", script);
                 std::process::exit(0)
             }
+            // during completion these are just words typed by the user
+            let action = match arg.split_once('=') {
+                _ if self.revision.is_some() => None,
+                Some(("--bpaf-complete-install", shell)) => Some((shell, true)),
+                Some(("--bpaf-complete-uninstall", shell)) => Some((shell, false)),
+                _ => None,
+            };
+            if let Some((shell, add)) = action {
+                let res = Dirs::from_env(|var| std::env::var(var).ok())
+                    .ok_or_else(|| "Can't find home directory, HOME is not set".to_owned())
+                    .and_then(|dirs| {
+                        if add {
+                            install(shell, name, self.protocol, &dirs)
+                        } else {
+                            uninstall(shell, name, &dirs)
+                        }
+                    });
+                match res {
+                    Ok(report) => {
+                        print!("{}", report);
+                        std::process::exit(0)
+                    }
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        std::process::exit(1)
+                    }
+                }
+            }
         }
         if let Some(ver) = arg.strip_prefix("--bpaf-complete-rev=") {
             if let Ok(ver) = ver.parse::<usize>() {
@@ -105,3 +304,66 @@ impl ArgScanner<'_> {
         Some(Complete::new(self.revision?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{env_protocol, nushell_completer, powershell_completer, ArgScanner, Protocol};
+    use crate::Args;
+
+    #[test]
+    fn powershell_script() {
+        let script = powershell_completer("app", Protocol::Flag);
+        assert!(script.starts_with("Register-ArgumentCompleter -Native -CommandName 'app' "));
+        assert!(script.contains("    $lines = & $program --bpaf-complete-rev=10 @rest\n"));
+        // output of the app is data, not code
+        assert!(!script.contains("Invoke-Expression"));
+
+        let script = powershell_completer("it\u{2019}s", Protocol::Env("APP_COMPLETE"));
+        assert!(script.contains("-CommandName 'it\u{2019}\u{2019}s' "));
+        assert!(script
+            .contains("    $env:APP_COMPLETE = 'powershell'\n    $lines = & $program @rest\n"));
+    }
+
+    #[test]
+    fn nushell_script() {
+        let script = nushell_completer("my-app", Protocol::Flag);
+        let expected = r#"let bpaf_my_app_fallback = $env.config.completions.external.completer?
+$env.config.completions.external = {
+    enable: true
+    completer: {|spans|
+        if $spans.0 == "my-app" {
+            ^$spans.0 --bpaf-complete-rev=11 ...($spans | skip 1) | from json
+        } else if $bpaf_my_app_fallback != null {
+            do $bpaf_my_app_fallback $spans
+        }
+    }
+}"#;
+        assert_eq!(script, expected);
+
+        let script = nushell_completer("a\"b\\c", Protocol::Flag);
+        assert!(script.contains(r#"if $spans.0 == "a\"b\\c" {"#));
+    }
+
+    #[test]
+    fn install_flags_are_words_during_completion() {
+        let mut scanner = ArgScanner {
+            revision: None,
+            name: Some("app"),
+            verbatim: false,
+            protocol: Protocol::Flag,
+        };
+        assert!(scanner.check_next("--bpaf-complete-rev=8".as_ref()));
+        assert!(!scanner.check_next("--bpaf-complete-install=bash".as_ref()));
+        assert!(!scanner.check_next("--bpaf-complete-uninstall=bash".as_ref()));
+        assert_eq!(scanner.revision, Some(8));
+    }
+
+    #[test]
+    fn env_protocol_is_remembered_for_install() {
+        let args = Args::from(&["--bpaf-complete-install=bash"]).set_name("app");
+        assert_eq!(args.c_env, None);
+        let args = env_protocol("BPAF_TEST_UNSET_COMPLETE", args).unwrap();
+        assert_eq!(args.c_env, Some("BPAF_TEST_UNSET_COMPLETE"));
+        assert_eq!(args.c_rev, None);
+    }
+}
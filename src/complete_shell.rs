@@ -1,8 +1,11 @@
 use std::borrow::Cow;
 
-use crate::{complete_gen::ShowComp, Error, Meta, Parser, State};
+use crate::{
+    complete_gen::{ShowComp, ShowKind},
+    Error, Meta, Parser, State,
+};
 
-struct Shell<'a>(&'a str);
+pub(crate) struct Shell<'a>(pub(crate) &'a str);
 
 impl std::fmt::Display for Shell<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -20,6 +23,67 @@ impl std::fmt::Display for Shell<'_> {
     }
 }
 
+/// Single quoted PowerShell string
+pub(crate) struct PowerShell<'a>(pub(crate) &'a str);
+
+impl std::fmt::Display for PowerShell<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        f.write_char('\'')?;
+        for c in self.0.chars() {
+            // PowerShell also treats typographic single quotes as quotes
+            if matches!(c, '\'' | '\u{2018}'..='\u{201B}') {
+                f.write_char(c)?;
+            }
+            f.write_char(c)?;
+        }
+        f.write_char('\'')?;
+        Ok(())
+    }
+}
+
+/// Double quoted Nushell string
+pub(crate) struct Nushell<'a>(pub(crate) &'a str);
+
+impl std::fmt::Display for Nushell<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\""),
+                '\\' => f.write_str("\\\\"),
+                c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32),
+                c => f.write_char(c),
+            }?;
+        }
+        f.write_char('"')?;
+        Ok(())
+    }
+}
+
+/// JSON string
+pub(crate) struct Json<'a>(pub(crate) &'a str);
+
+impl std::fmt::Display for Json<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\""),
+                '\\' => f.write_str("\\\\"),
+                '\n' => f.write_str("\\n"),
+                '\t' => f.write_str("\\t"),
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32),
+                c => f.write_char(c),
+            }?;
+        }
+        f.write_char('"')?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 /// Shell specific completion
 #[non_exhaustive]
@@ -44,7 +108,8 @@ pub enum ShellComp {
     /// You can also specify a raw value to use for each supported shell
     ///
     /// It is possible to fill in values for shells you don't want to support
-    /// with empty strings but the code is not going to work for those shells
+    /// with empty strings but the code is not going to work for those shells.
+    /// `powershell` completes file names instead and `nushell` ignores raw values
     Raw {
         /// This raw string will be used for `bash` shell
         /// <https://www.gnu.org/software/bash/manual/html_node/Command-Line-Editing.html>
@@ -109,7 +174,7 @@ where
     }
 
     fn meta(&self) -> Meta {
-        self.inner.meta()
+        Meta::Comp(Box::new(self.inner.meta()), Some(self.op))
     }
 }
 
@@ -117,12 +182,21 @@ pub(crate) fn render_zsh(
     items: &[ShowComp],
     ops: &[ShellComp],
     full_lit: &str,
+    prefix: &str,
+    fuzzy: bool,
 ) -> Result<String, std::fmt::Error> {
     use std::fmt::Write;
     let mut res = String::new();
+    // zsh filters out candidates that don't start with the typed text unless asked not to
+    let compadd = if fuzzy { "compadd -U" } else { "compadd" };
 
     if items.is_empty() && ops.is_empty() {
-        return Ok(format!("compadd -- {}\n", full_lit));
+        return Ok(format!("{} -- {}\n", compadd, full_lit));
+    }
+
+    // file names go after "--key=" or "-k" part of the word
+    if !ops.is_empty() && !prefix.is_empty() {
+        writeln!(res, "compset -P {}", Shell(prefix))?;
     }
 
     for op in ops {
@@ -138,11 +212,15 @@ pub(crate) fn render_zsh(
 
     if items.len() == 1 {
         if items[0].subst.is_empty() {
-            writeln!(res, "compadd -- {}", Shell(items[0].pretty.as_str()))?;
+            writeln!(res, "{} -- {}", compadd, Shell(items[0].pretty.as_str()))?;
             writeln!(res, "compadd ''")?;
             return Ok(res);
         } else {
-            return Ok(format!("compadd -- {}\n", Shell(items[0].subst.as_str())));
+            return Ok(format!(
+                "{} -- {}\n",
+                compadd,
+                Shell(items[0].subst.as_str())
+            ));
         }
     }
     writeln!(res, "local -a descr")?;
@@ -153,7 +231,8 @@ pub(crate) fn render_zsh(
         if let Some(group) = &item.extra.group {
             writeln!(
                 res,
-                "compadd -l -d descr -V {} -X {} -- {}",
+                "{} -l -d descr -V {} -X {} -- {}",
+                compadd,
                 Shell(group),
                 Shell(group),
                 Shell(&item.subst),
@@ -164,7 +243,8 @@ pub(crate) fn render_zsh(
             // does nothing, while `-V whatever` stops sorting...
             writeln!(
                 res,
-                "compadd -l -V nosort -d descr -- {}",
+                "{} -l -V nosort -d descr -- {}",
+                compadd,
                 Shell(&item.subst)
             )?;
         }
@@ -176,6 +256,7 @@ pub(crate) fn render_bash(
     items: &[ShowComp],
     ops: &[ShellComp],
     full_lit: &str,
+    prefix: &str,
 ) -> Result<String, std::fmt::Error> {
     // Bash is strange when it comes to completion - rather than taking
     // a glob - _filedir takes an extension which it later to include uppercase
@@ -223,11 +304,20 @@ pub(crate) fn render_bash(
         }?;
     }
 
+    // bash splits words at "=" and replaces only the part after it, the rest of
+    // the prefix such as "-k" in "-kval" is replaced and must be kept
+    let split = prefix.rfind('=').map_or(0, |ix| ix + 1);
+    let keep = &prefix[split..];
+
     if items.len() == 1 {
         if items[0].subst.is_empty() {
             writeln!(res, "COMPREPLY+=( {} '')", Shell(&items[0].pretty))?;
         } else {
-            writeln!(res, "COMPREPLY+=( {} )\n", Shell(&items[0].subst))?;
+            let subst = match items[0].kind {
+                ShowKind::Value => &items[0].subst[split..],
+                ShowKind::Flag | ShowKind::Command => items[0].subst.as_str(),
+            };
+            writeln!(res, "COMPREPLY+=( {} )\n", Shell(subst))?;
         }
 
         return Ok(res);
@@ -240,7 +330,11 @@ pub(crate) fn render_bash(
                 writeln!(res, "COMPREPLY+=({})", Shell(group))?;
             }
         }
-        writeln!(res, "COMPREPLY+=({})", Shell(&item.to_string()))?;
+        let shown = match item.kind {
+            ShowKind::Value if !item.subst.is_empty() => format!("{}{}", keep, item),
+            _ => item.to_string(),
+        };
+        writeln!(res, "COMPREPLY+=({})", Shell(&shown))?;
     }
 
     Ok(res)
@@ -294,7 +388,9 @@ pub(crate) fn render_fish(
     let shared = if ops.is_empty() { "-f " } else { "" };
     for item in items.iter().rev().filter(|i| !i.subst.is_empty()) {
         write!(res, "complete -c {} {}", app, shared)?;
-        if let Some(long) = item.subst.strip_prefix("--") {
+        if item.kind == ShowKind::Value {
+            write!(res, "-a {} ", item.subst)?;
+        } else if let Some(long) = item.subst.strip_prefix("--") {
             write!(res, "--long-option {} ", long)?;
         } else if let Some(short) = item.subst.strip_prefix('-') {
             write!(res, "--short-option {} ", short)?;
@@ -310,6 +406,201 @@ pub(crate) fn render_fish(
     Ok(res)
 }
 
+pub(crate) fn render_powershell(
+    items: &[ShowComp],
+    ops: &[ShellComp],
+    full_lit: &str,
+    prefix: &str,
+) -> Result<String, std::fmt::Error> {
+    use std::fmt::Write;
+    let mut res = String::new();
+    // Completion script reads tab separated lines and creates completion results itself, one
+    // of `ParameterValue`, `ParameterName` or `Text` followed by text to insert, text to show
+    // and a tooltip; or `Files`/`Dirs` followed by a word to complete file names for, a mask
+    // and a prefix to add back to the results. Nothing here is evaluated as code.
+    let field = |s: &str| s.replace(|c| matches!(c, '\t' | '\n' | '\r'), " ");
+
+    if items.is_empty() && ops.is_empty() {
+        let lit = field(full_lit);
+        writeln!(res, "Text\t{}\t{}\t{}", lit, lit, lit)?;
+        return Ok(res);
+    }
+
+    // file names replace the whole word so "--key=" or "-k" part must be added back
+    let word = field(&full_lit[prefix.len()..]);
+    let prefix = field(prefix);
+    for op in ops {
+        match op {
+            // PowerShell falls back to file names when nothing else matches,
+            // raw values for other shells are the closest to that
+            ShellComp::File { mask: None } | ShellComp::Raw { .. } => {
+                writeln!(res, "Files\t{}\t\t{}", word, prefix)
+            }
+            ShellComp::File { mask: Some(mask) } => {
+                writeln!(res, "Files\t{}\t{}\t{}", word, field(mask), prefix)
+            }
+            ShellComp::Dir { mask } => writeln!(
+                res,
+                "Dirs\t{}\t{}\t{}",
+                word,
+                field(mask.unwrap_or("")),
+                prefix
+            ),
+            ShellComp::Nothing => Ok(()),
+        }?;
+    }
+
+    for item in items {
+        // metavariables can't be inserted, keep what user typed and show them in the menu
+        let (subst, kind) = if item.subst.is_empty() {
+            (full_lit, "Text")
+        } else if item.kind == ShowKind::Value {
+            (item.subst.as_str(), "ParameterValue")
+        } else {
+            (item.subst.as_str(), "ParameterName")
+        };
+        // tooltip must not be empty
+        let tooltip = match item.extra.help.as_deref() {
+            Some(help) if !help.is_empty() => help,
+            _ => item.pretty.as_str(),
+        };
+        writeln!(
+            res,
+            "{}\t{}\t{}\t{}",
+            kind,
+            field(subst),
+            field(&item.pretty),
+            field(tooltip)
+        )?;
+    }
+    Ok(res)
+}
+
+/// Check if file name matches a shell style mask
+///
+/// Supports `*`, `?` and alternatives in the form of `*.(rs|toml)`
+fn mask_matches(mask: &str, name: &str) -> bool {
+    if let Some(start) = mask.find('(') {
+        if let Some(len) = mask[start..].find(')') {
+            let (head, tail) = (&mask[..start], &mask[start + len + 1..]);
+            return mask[start + 1..start + len]
+                .split('|')
+                .any(|alt| mask_matches(&format!("{}{}{}", head, alt, tail), name));
+        }
+    }
+    let mut chars = name.chars();
+    let mut mask_chars = mask.chars();
+    loop {
+        match mask_chars.next() {
+            None => return chars.next().is_none(),
+            Some('*') => {
+                let rest = mask_chars.as_str();
+                let name = chars.as_str();
+                return name
+                    .char_indices()
+                    .map(|(ix, _)| ix)
+                    .chain(std::iter::once(name.len()))
+                    .any(|ix| mask_matches(rest, &name[ix..]));
+            }
+            Some('?') => {
+                if chars.next().is_none() {
+                    return false;
+                }
+            }
+            Some(c) => {
+                if chars.next() != Some(c) {
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// List files and directories that start with `lit`, directories end with `/`
+///
+/// `files` - include files, not just directories, `mask` applies to files if they are included
+/// and to directories otherwise
+fn list_files(lit: &str, files: bool, mask: Option<&str>) -> Vec<String> {
+    let (dir, prefix) = match lit.rfind('/') {
+        Some(ix) => (&lit[..=ix], &lit[ix + 1..]),
+        None => ("", lit),
+    };
+    let entries = match std::fs::read_dir(if dir.is_empty() { "." } else { dir }) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut res = Vec::new();
+    for entry in entries.flatten() {
+        let name = match entry.file_name().into_string() {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+        if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+            continue;
+        }
+        let is_dir = entry.path().is_dir();
+        let keep = match (is_dir, files, mask) {
+            (true, true, _) | (_, _, None) => is_dir || files,
+            (_, _, Some(mask)) => is_dir != files && mask_matches(mask, &name),
+        };
+        if keep {
+            res.push(format!("{}{}{}", dir, name, if is_dir { "/" } else { "" }));
+        }
+    }
+    res.sort();
+    res
+}
+
+pub(crate) fn render_nushell(
+    items: &[ShowComp],
+    ops: &[ShellComp],
+    full_lit: &str,
+    prefix: &str,
+) -> Result<String, std::fmt::Error> {
+    use std::fmt::Write;
+    let mut res = String::new();
+    let mut first = true;
+    let mut push = |res: &mut String, value: &str, descr: Option<&str>| {
+        res.push_str(if first { "[\n" } else { ",\n" });
+        first = false;
+        write!(res, "{{\"value\": {}", Json(value))?;
+        if let Some(descr) = descr {
+            write!(res, ", \"description\": {}", Json(descr))?;
+        }
+        res.push('}');
+        Ok::<(), std::fmt::Error>(())
+    };
+
+    if items.is_empty() && ops.is_empty() {
+        push(&mut res, full_lit, None)?;
+    }
+
+    // nushell can't filter files by mask so bpaf lists them itself
+    let lit = &full_lit[prefix.len()..];
+    for op in ops {
+        let files = match op {
+            ShellComp::File { mask } => list_files(lit, true, *mask),
+            ShellComp::Dir { mask } => list_files(lit, false, *mask),
+            ShellComp::Raw { .. } | ShellComp::Nothing => Vec::new(),
+        };
+        for file in files {
+            push(&mut res, &format!("{}{}", prefix, file), None)?;
+        }
+    }
+
+    for item in items {
+        // metavariables can't be inserted, keep what user typed and show them as description
+        if item.subst.is_empty() {
+            push(&mut res, full_lit, Some(&item.to_string()))?;
+        } else {
+            push(&mut res, &item.subst, item.extra.help.as_deref())?;
+        }
+    }
+
+    res.push_str(if first { "[]\n" } else { "\n]\n" });
+    Ok(res)
+}
+
 pub(crate) fn render_simple(items: &[ShowComp]) -> Result<String, std::fmt::Error> {
     use std::fmt::Write;
     let mut res = String::new();
@@ -0,0 +1,413 @@
+//! Completion specs for external completion engines
+//!
+//! Fig and Inshellisense use TypeScript specs, Carapace uses YAML specs. Both are derived from
+//! the same information as static completion scripts. Values from
+//! [`complete`](crate::Parser::complete) functions are produced by generators that call the app,
+//! by default with the runtime completion protocol.
+
+use crate::{
+    complete_shell::{Json, Shell},
+    complete_static::{expand_mask, levels, mask_regex, Hint, Level},
+    OptionParser, ShellComp,
+};
+use std::fmt::Write;
+
+/// Words to pass to the app before the value to complete: app name, command names and
+/// a flag name for arguments
+fn context<'a>(level: &'a Level, name: Option<&'a str>) -> Vec<&'a str> {
+    let mut res = level.path.split(' ').collect::<Vec<_>>();
+    res.extend(name);
+    res
+}
+
+/// Levels for commands of a given level, in order
+fn children<'a>(levels: &'a [Level], level: &'a Level) -> impl Iterator<Item = &'a Level> {
+    level.commands.iter().filter_map(move |cmd| {
+        let path = format!("{} {}", level.path, cmd.names[0]);
+        levels.iter().find(|l| l.path == path)
+    })
+}
+
+const FIG_GENERATOR: &str = r#"const bpafGenerator: Fig.Generator = {
+  script: (tokens) => [tokens[0], "--bpaf-complete-rev=1", ...tokens.slice(1)],
+  postProcess: (out) =>
+    out
+      .split("\n")
+      .filter((line) => !(line === "" || line.startsWith("\t")))
+      .map((line) => {
+        const [name, description] = line.split("\t");
+        return { name, description };
+      }),
+};
+"#;
+
+/// Fig spec fields that describe how to complete a value
+fn fig_hint(hint: Hint, generator: &dyn Fn(&[&str]) -> String, words: &[&str]) -> Option<String> {
+    let filter = |template: &str, mask: &str| {
+        format!(
+            "generators: {{\n  template: {:?},\n  filterTemplateSuggestions: (paths) =>\n    paths.filter((p) => {}new RegExp({}).test(p.name.replace(/\\/$/, \"\"))),\n}}",
+            template,
+            if template == "filepaths" { "p.type === \"folder\" || " } else { "" },
+            Json(&mask_regex(mask))
+        )
+    };
+    match hint {
+        Hint::Nothing | Hint::Shell(ShellComp::Nothing) | Hint::Shell(ShellComp::Raw { .. }) => {
+            None
+        }
+        Hint::Shell(ShellComp::File { mask: None }) => Some("template: \"filepaths\"".to_owned()),
+        Hint::Shell(ShellComp::Dir { mask: None }) => Some("template: \"folders\"".to_owned()),
+        Hint::Shell(ShellComp::File { mask: Some(m) }) => Some(filter("filepaths", m)),
+        Hint::Shell(ShellComp::Dir { mask: Some(m) }) => Some(filter("folders", m)),
+        Hint::Dynamic => Some(format!("generators: {}", generator(words))),
+        Hint::OneOf(values) => {
+            let mut res = String::from("suggestions: [\n");
+            for (value, help) in values {
+                match help {
+                    Some(help) => writeln!(
+                        res,
+                        "  {{ name: {}, description: {} }},",
+                        Json(value),
+                        Json(help)
+                    ),
+                    None => writeln!(res, "  {{ name: {} }},", Json(value)),
+                }
+                .unwrap();
+            }
+            res.push(']');
+            Some(res)
+        }
+    }
+}
+
+/// Write a multiline value with all the lines but the first one indented
+fn indented(w: &mut String, indent: &str, text: &str) {
+    for (ix, line) in text.lines().enumerate() {
+        if ix > 0 {
+            w.push('\n');
+            w.push_str(indent);
+        }
+        w.push_str(line);
+    }
+}
+
+/// Write an argument object, `label` goes in front of it on the same line
+fn fig_arg(
+    w: &mut String,
+    indent: &str,
+    label: &str,
+    pos: (&str, &Option<String>, bool),
+    hint: Option<String>,
+) {
+    let (name, help, many) = pos;
+    writeln!(w, "{}{}{{", indent, label).unwrap();
+    writeln!(w, "{}  name: {},", indent, Json(name)).unwrap();
+    if let Some(help) = help {
+        writeln!(w, "{}  description: {},", indent, Json(help)).unwrap();
+    }
+    if many {
+        writeln!(w, "{}  isVariadic: true,", indent).unwrap();
+    }
+    if let Some(hint) = hint {
+        write!(w, "{}  ", indent).unwrap();
+        indented(w, &format!("{}  ", indent), &hint);
+        writeln!(w, ",").unwrap();
+    }
+    write!(w, "{}}}", indent).unwrap();
+}
+
+fn fig_level(
+    w: &mut String,
+    levels: &[Level],
+    level: &Level,
+    names: &[String],
+    help: &Option<String>,
+    indent: &str,
+    generator: &dyn Fn(&[&str]) -> String,
+) {
+    let inner = format!("{}  ", indent);
+    let item = format!("{}  ", inner);
+    match names {
+        [name] => writeln!(w, "{}name: {},", indent, Json(name)),
+        _ => {
+            let names = names
+                .iter()
+                .map(|n| Json(n).to_string())
+                .collect::<Vec<_>>();
+            writeln!(w, "{}name: [{}],", indent, names.join(", "))
+        }
+    }
+    .unwrap();
+    if let Some(help) = help {
+        writeln!(w, "{}description: {},", indent, Json(help)).unwrap();
+    }
+
+    if !level.named.is_empty() {
+        writeln!(w, "{}options: [", indent).unwrap();
+        for named in &level.named {
+            writeln!(w, "{}{{", inner).unwrap();
+            let names = named
+                .names
+                .iter()
+                .map(|n| Json(n).to_string())
+                .collect::<Vec<_>>();
+            if names.len() == 1 {
+                writeln!(w, "{}name: {},", item, names[0]).unwrap();
+            } else {
+                writeln!(w, "{}name: [{}],", item, names.join(", ")).unwrap();
+            }
+            if let Some(help) = &named.help {
+                writeln!(w, "{}description: {},", item, Json(help)).unwrap();
+            }
+            if let (Some(hint), Some(metavar)) = (named.value, &named.metavar) {
+                let words = context(level, Some(&named.names[0]));
+                let hint = fig_hint(hint, generator, &words);
+                fig_arg(w, &item, "args: ", (metavar, &None, false), hint);
+                writeln!(w, ",").unwrap();
+            }
+            writeln!(w, "{}}},", inner).unwrap();
+        }
+        writeln!(w, "{}],", indent).unwrap();
+    }
+
+    if !level.positionals.is_empty() {
+        writeln!(w, "{}args: [", indent).unwrap();
+        for pos in &level.positionals {
+            let hint = fig_hint(pos.hint, generator, &context(level, None));
+            fig_arg(w, &inner, "", (&pos.metavar, &pos.help, pos.many), hint);
+            writeln!(w, ",").unwrap();
+        }
+        writeln!(w, "{}],", indent).unwrap();
+    }
+
+    if !level.commands.is_empty() {
+        writeln!(w, "{}subcommands: [", indent).unwrap();
+        for (cmd, child) in level.commands.iter().zip(children(levels, level)) {
+            writeln!(w, "{}{{", inner).unwrap();
+            fig_level(w, levels, child, &cmd.names, &cmd.help, &item, generator);
+            writeln!(w, "{}}},", inner).unwrap();
+        }
+        writeln!(w, "{}],", indent).unwrap();
+    }
+}
+
+/// Carapace macros or values that describe how to complete a value, as YAML list items
+fn carapace_hint(
+    hint: Hint,
+    generator: &dyn Fn(&[&str]) -> String,
+    words: &[&str],
+) -> Option<String> {
+    let action = match hint {
+        Hint::Nothing | Hint::Shell(ShellComp::Nothing) | Hint::Shell(ShellComp::Raw { .. }) => {
+            return None
+        }
+        Hint::Shell(ShellComp::File { mask: None }) => "$files".to_owned(),
+        Hint::Shell(ShellComp::File { mask: Some(m) }) => {
+            // carapace can only filter files by suffix
+            let masks = expand_mask(m);
+            let suffixes = masks
+                .iter()
+                .map(|m| m.strip_prefix('*'))
+                .collect::<Option<Vec<_>>>();
+            match suffixes {
+                Some(suffixes) => format!("$files([{}])", suffixes.join(", ")),
+                None => "$files".to_owned(),
+            }
+        }
+        Hint::Shell(ShellComp::Dir { .. }) => "$directories".to_owned(),
+        Hint::Dynamic => generator(words),
+        // values with descriptions are separated by a tab
+        Hint::OneOf(values) => {
+            let values = values
+                .iter()
+                .map(|(value, help)| match help {
+                    Some(help) => Json(&format!("{}\t{}", value, help)).to_string(),
+                    None => Json(value).to_string(),
+                })
+                .collect::<Vec<_>>();
+            return Some(values.join(", "));
+        }
+    };
+    Some(Json(&action).to_string())
+}
+
+/// Write a command, first line is not indented so it can be a list item
+fn carapace_level(
+    w: &mut String,
+    levels: &[Level],
+    level: &Level,
+    names: &[String],
+    help: &Option<String>,
+    indent: &str,
+    generator: &dyn Fn(&[&str]) -> String,
+) {
+    let inner = format!("{}  ", indent);
+    let item = format!("{}  ", inner);
+    writeln!(w, "name: {}", Json(&names[0])).unwrap();
+    if names.len() > 1 {
+        let aliases = names[1..]
+            .iter()
+            .map(|n| Json(n).to_string())
+            .collect::<Vec<_>>();
+        writeln!(w, "{}aliases: [{}]", indent, aliases.join(", ")).unwrap();
+    }
+    if let Some(help) = help {
+        writeln!(w, "{}description: {}", indent, Json(help)).unwrap();
+    }
+
+    if !level.named.is_empty() {
+        writeln!(w, "{}flags:", indent).unwrap();
+        for named in &level.named {
+            // carapace wants short name first and marks arguments with `=`
+            let mut key = named
+                .names
+                .iter()
+                .rev()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if named.value.is_some() {
+                key.push('=');
+            }
+            let help = named.help.as_deref().unwrap_or("");
+            writeln!(w, "{}{}: {}", inner, Json(&key), Json(help)).unwrap();
+        }
+    }
+
+    let mut flags = Vec::new();
+    for named in &level.named {
+        if let Some(hint) = named.value {
+            let words = context(level, Some(&named.names[0]));
+            if let Some(hint) = carapace_hint(hint, generator, &words) {
+                let name = named.names[0].trim_start_matches('-');
+                flags.push((name, hint));
+            }
+        }
+    }
+    let mut positional = level
+        .positionals
+        .iter()
+        .map(|pos| carapace_hint(pos.hint, generator, &context(level, None)))
+        .collect::<Vec<_>>();
+    let any = match level.positionals.last() {
+        Some(pos) if pos.many => positional.pop().flatten(),
+        _ => None,
+    };
+    while let Some(None) = positional.last() {
+        positional.pop();
+    }
+    if !(flags.is_empty() && positional.is_empty() && any.is_none()) {
+        writeln!(w, "{}completion:", indent).unwrap();
+        if !flags.is_empty() {
+            writeln!(w, "{}flag:", inner).unwrap();
+            for (name, hint) in flags {
+                writeln!(w, "{}{}: [{}]", item, Json(name), hint).unwrap();
+            }
+        }
+        if !positional.is_empty() {
+            writeln!(w, "{}positional:", inner).unwrap();
+            for hint in positional {
+                match hint {
+                    Some(hint) => writeln!(w, "{}- [{}]", item, hint),
+                    None => writeln!(w, "{}- []", item),
+                }
+                .unwrap();
+            }
+        }
+        if let Some(hint) = any {
+            writeln!(w, "{}positionalany: [{}]", inner, hint).unwrap();
+        }
+    }
+
+    if !level.commands.is_empty() {
+        writeln!(w, "{}commands:", indent).unwrap();
+        for (cmd, child) in level.commands.iter().zip(children(levels, level)) {
+            write!(w, "{}- ", inner).unwrap();
+            carapace_level(w, levels, child, &cmd.names, &cmd.help, &item, generator);
+        }
+    }
+}
+
+impl<T> OptionParser<T> {
+    /// Render completion spec for Fig and Inshellisense
+    ///
+    /// Spec is a TypeScript module that exports the spec object with all the flags, arguments
+    /// and commands of the parser. Values from [`complete`](crate::Parser::complete) functions
+    /// are produced by a generator that calls the app with the runtime completion protocol.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v').long("verbose").help("Be verbose").switch().to_options();
+    /// let spec = parser.render_fig_spec("app");
+    /// assert!(spec.contains(r#"name: ["--verbose", "-v"],"#));
+    /// ```
+    pub fn render_fig_spec(&self, app: impl AsRef<str>) -> String {
+        let mut res = self.render_fig_spec_with(app, |_| "bpafGenerator".to_owned());
+        res.insert_str(0, &format!("{}\n", FIG_GENERATOR));
+        res
+    }
+
+    /// Render completion spec for Fig and Inshellisense with a custom generator
+    ///
+    /// `generator` is called for every value from a [`complete`](crate::Parser::complete)
+    /// function with words the app needs before that value: app name, command names and
+    /// the argument name for named arguments. It should return a TypeScript expression
+    /// for `generators` field, see [`render_fig_spec`](OptionParser::render_fig_spec).
+    pub fn render_fig_spec_with<F>(&self, app: impl AsRef<str>, generator: F) -> String
+    where
+        F: Fn(&[&str]) -> String,
+    {
+        let levels = levels(self, app.as_ref());
+        let help = self.info.descr.as_ref().and_then(|d| d.to_completion());
+        let mut res = String::new();
+        let w = &mut res;
+        writeln!(w, "const completionSpec: Fig.Spec = {{").unwrap();
+        let names = [app.as_ref().to_owned()];
+        fig_level(w, &levels, &levels[0], &names, &help, "  ", &generator);
+        writeln!(w, "}};").unwrap();
+        writeln!(w).unwrap();
+        writeln!(w, "export default completionSpec;").unwrap();
+        res
+    }
+
+    /// Render completion spec for Carapace
+    ///
+    /// Spec is a YAML document with all the flags, arguments and commands of the parser.
+    /// Values from [`complete`](crate::Parser::complete) functions are produced by calling
+    /// the app with the runtime completion protocol.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v').long("verbose").help("Be verbose").switch().to_options();
+    /// let spec = parser.render_carapace_spec("app");
+    /// assert!(spec.contains(r#""-v, --verbose": "Be verbose""#));
+    /// ```
+    pub fn render_carapace_spec(&self, app: impl AsRef<str>) -> String {
+        self.render_carapace_spec_with(app, |words| {
+            let mut cmd = format!("{} --bpaf-complete-rev=1", Shell(words[0]));
+            for word in &words[1..] {
+                write!(cmd, " {}", Shell(word)).unwrap();
+            }
+            // lines with no value are metavariables
+            format!("$({} \"$C_VALUE\" | awk -F '\\t' '$1 != \"\"')", cmd)
+        })
+    }
+
+    /// Render completion spec for Carapace with a custom generator
+    ///
+    /// `generator` is called for every value from a [`complete`](crate::Parser::complete)
+    /// function with words the app needs before that value: app name, command names and
+    /// the argument name for named arguments. It should return a Carapace macro such as
+    /// `$(command)`, see [`render_carapace_spec`](OptionParser::render_carapace_spec).
+    pub fn render_carapace_spec_with<F>(&self, app: impl AsRef<str>, generator: F) -> String
+    where
+        F: Fn(&[&str]) -> String,
+    {
+        let levels = levels(self, app.as_ref());
+        let help = self.info.descr.as_ref().and_then(|d| d.to_completion());
+        let mut res = String::new();
+        let names = [app.as_ref().to_owned()];
+        carapace_level(&mut res, &levels, &levels[0], &names, &help, "", &generator);
+        res
+    }
+}
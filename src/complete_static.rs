@@ -0,0 +1,842 @@
+//! Static completion scripts
+//!
+//! Unlike dynamic completion scripts that call the app on every TAB, static scripts contain
+//! all the flags, arguments and commands from the parser. The app is called only to complete
+//! values that come from [`complete`](crate::Parser::complete) functions.
+
+use crate::{
+    complete_shell::Shell,
+    info::Info,
+    item::{Item, ShortLong},
+    Doc, Meta, OptionParser, Parser, ShellComp, TypeHint,
+};
+use std::fmt::Write;
+
+/// What to do to complete a value
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Hint {
+    /// No completion
+    Nothing,
+    /// Shell specific completion
+    Shell(ShellComp),
+    /// Ask the app with dynamic completion
+    Dynamic,
+    /// One of the listed values, from the value type
+    OneOf(&'static [(&'static str, Option<&'static str>)]),
+}
+
+impl Hint {
+    /// Use hint from the value type unless completion for the item is set explicitly
+    fn or_type(self, hint: Option<TypeHint>) -> Self {
+        match (self, hint) {
+            (Hint::Nothing, Some(TypeHint::File)) => Hint::Shell(ShellComp::File { mask: None }),
+            (Hint::Nothing, Some(TypeHint::Dir)) => Hint::Shell(ShellComp::Dir { mask: None }),
+            (Hint::Nothing, Some(TypeHint::OneOf(values))) => Hint::OneOf(values),
+            (hint, _) => hint,
+        }
+    }
+}
+
+/// Flag or an argument
+pub(crate) struct Named {
+    /// Long name first if present
+    pub(crate) names: Vec<String>,
+    pub(crate) help: Option<String>,
+    /// Arguments need a value
+    pub(crate) value: Option<Hint>,
+    pub(crate) metavar: Option<String>,
+}
+
+pub(crate) struct Command {
+    pub(crate) names: Vec<String>,
+    pub(crate) help: Option<String>,
+}
+
+pub(crate) struct Positional {
+    pub(crate) metavar: String,
+    pub(crate) help: Option<String>,
+    pub(crate) hint: Hint,
+    /// Item can be repeated
+    pub(crate) many: bool,
+}
+
+/// Items available to a single command
+pub(crate) struct Level {
+    /// App name followed by command names
+    pub(crate) path: String,
+    pub(crate) named: Vec<Named>,
+    pub(crate) commands: Vec<Command>,
+    /// Positional items in order
+    pub(crate) positionals: Vec<Positional>,
+}
+
+fn help_line(help: &Option<Doc>) -> Option<String> {
+    help.as_ref().and_then(Doc::to_completion)
+}
+
+fn names(name: ShortLong) -> Vec<String> {
+    match name {
+        ShortLong::Short(s) => vec![format!("-{}", s)],
+        ShortLong::Long(l) => vec![format!("--{}", l)],
+        ShortLong::Both(s, l) => vec![format!("--{}", l), format!("-{}", s)],
+    }
+}
+
+fn collect_items<'a>(
+    meta: &'a Meta,
+    hint: Hint,
+    many: bool,
+    level: &mut Level,
+    commands: &mut Vec<&'a Item>,
+) {
+    match meta {
+        Meta::And(xs) | Meta::Or(xs) => {
+            for x in xs {
+                collect_items(x, hint, many, level, commands);
+            }
+        }
+        Meta::Optional(m)
+        | Meta::Required(m)
+        | Meta::Adjacent(m)
+        | Meta::Subsection(m, _)
+        | Meta::Suffix(m, _)
+        | Meta::CustomUsage(m, _)
+        | Meta::Strict(m) => collect_items(m, hint, many, level, commands),
+        Meta::Many(m) => collect_items(m, hint, true, level, commands),
+        Meta::Comp(m, op) => {
+            let hint = op.map_or(Hint::Dynamic, Hint::Shell);
+            collect_items(m, hint, many, level, commands);
+        }
+        Meta::Item(item) => match item.as_ref() {
+            Item::Flag { name, help, .. } => level.named.push(Named {
+                names: names(*name),
+                help: help_line(help),
+                value: None,
+                metavar: None,
+            }),
+            Item::Argument {
+                name,
+                help,
+                metavar,
+                hint: type_hint,
+                ..
+            } => level.named.push(Named {
+                names: names(*name),
+                help: help_line(help),
+                value: Some(hint.or_type(*type_hint)),
+                metavar: Some(metavar.0.to_owned()),
+            }),
+            Item::Positional {
+                metavar,
+                help,
+                hint: type_hint,
+            } => level.positionals.push(Positional {
+                metavar: metavar.0.to_owned(),
+                help: help_line(help),
+                hint: hint.or_type(*type_hint),
+                many,
+            }),
+            Item::Any { metavar, help, .. } => level.positionals.push(Positional {
+                metavar: metavar.monochrome(false),
+                help: help_line(help),
+                hint,
+                many,
+            }),
+            Item::Command { .. } => commands.push(item),
+        },
+        Meta::Skip => {}
+    }
+}
+
+fn collect_levels(meta: &Meta, info: &Info, path: String, levels: &mut Vec<Level>) {
+    let mut level = Level {
+        path,
+        named: Vec::new(),
+        commands: Vec::new(),
+        positionals: Vec::new(),
+    };
+    let extra = info.meta();
+    let mut commands = Vec::new();
+    collect_items(meta, Hint::Nothing, false, &mut level, &mut commands);
+    collect_items(&extra, Hint::Nothing, false, &mut level, &mut commands);
+
+    let mut children = Vec::new();
+    for item in commands {
+        if let Item::Command {
+            name,
+            short,
+            help,
+            meta,
+            info,
+            ..
+        } = item
+        {
+            let mut names = vec![(*name).to_owned()];
+            names.extend(short.map(|s| s.to_string()));
+            level.commands.push(Command {
+                names,
+                help: help_line(help),
+            });
+            children.push((format!("{} {}", level.path, name), meta, info));
+        }
+    }
+    levels.push(level);
+    for (path, meta, info) in children {
+        collect_levels(meta, info, path, levels);
+    }
+}
+
+/// Items of every command in the parser, parents go before their children
+pub(crate) fn levels<T>(parser: &OptionParser<T>, app: &str) -> Vec<Level> {
+    let mut levels = Vec::new();
+    collect_levels(
+        &parser.inner.meta(),
+        &parser.info,
+        app.to_owned(),
+        &mut levels,
+    );
+    levels
+}
+
+/// Name usable as a part of shell function name
+fn ident(app: &str) -> String {
+    app.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+}
+
+/// Single quoted fish string
+struct Fish<'a>(&'a str);
+
+impl std::fmt::Display for Fish<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('\'')?;
+        for c in self.0.chars() {
+            if c == '\'' || c == '\\' {
+                f.write_char('\\')?;
+            }
+            f.write_char(c)?;
+        }
+        f.write_char('\'')
+    }
+}
+
+/// `case` patterns that match any name of an item in a given command
+fn patterns(path: &str, names: &[String], sep: &str, fish: bool) -> String {
+    let mut res = String::new();
+    for (ix, name) in names.iter().enumerate() {
+        if ix > 0 {
+            res.push_str(sep);
+        }
+        let pat = format!("{}:{}", path, name);
+        if fish {
+            write!(res, "{}", Fish(&pat))
+        } else {
+            write!(res, "{}", Shell(&pat))
+        }
+        .unwrap();
+    }
+    res
+}
+
+/// Long names of an item, only they can take a value in `--key=value` form
+fn long_names(named: &Named) -> Vec<String> {
+    named
+        .names
+        .iter()
+        .filter(|n| n.starts_with("--"))
+        .cloned()
+        .collect()
+}
+
+/// Arguments that take a value, along with their canonical names
+fn arguments(level: &Level) -> impl Iterator<Item = (&Named, String, Hint)> {
+    level.named.iter().filter_map(move |n| {
+        let hint = n.value?;
+        Some((n, format!("{} {}", level.path, n.names[0]), hint))
+    })
+}
+
+/// Branches for positional items, `*` covers the rest if the last item repeats
+fn positional_branches(level: &Level) -> Vec<(String, Hint)> {
+    let mut res = Vec::new();
+    let len = level.positionals.len();
+    for (ix, pos) in level.positionals.iter().enumerate() {
+        if matches!(pos.hint, Hint::Nothing) {
+            continue;
+        }
+        if pos.many && ix + 1 == len {
+            res.push(("*".to_owned(), pos.hint));
+        } else {
+            res.push((ix.to_string(), pos.hint));
+        }
+    }
+    res
+}
+
+/// Expand alternatives in a mask: `*.(rs|toml)` becomes `*.rs` and `*.toml`
+pub(crate) fn expand_mask(mask: &str) -> Vec<String> {
+    if let Some(start) = mask.find('(') {
+        if let Some(len) = mask[start..].find(')') {
+            let (head, tail) = (&mask[..start], &mask[start + len + 1..]);
+            return mask[start + 1..start + len]
+                .split('|')
+                .flat_map(|alt| expand_mask(&format!("{}{}{}", head, alt, tail)))
+                .collect();
+        }
+    }
+    vec![mask.to_owned()]
+}
+
+fn bash_hint(hint: Hint, app: &str) -> String {
+    let add = format!("_bpaf_{}_add", app);
+    // one call per alternative, bash needs extglob to handle them otherwise
+    let masked = |flag: &str, mask: &str| {
+        expand_mask(mask)
+            .iter()
+            .map(|m| format!("{} {} -X {}", add, flag, Shell(&format!("!{}", m))))
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+    match hint {
+        Hint::Nothing | Hint::Shell(ShellComp::Nothing) => String::new(),
+        Hint::Shell(ShellComp::File { mask: None }) => {
+            format!("compopt -o filenames 2>/dev/null; {} -f", add)
+        }
+        Hint::Shell(ShellComp::File { mask: Some(m) }) => format!(
+            "compopt -o filenames 2>/dev/null; {} -d; {}",
+            add,
+            masked("-f", m)
+        ),
+        Hint::Shell(ShellComp::Dir { mask: None }) => {
+            format!("compopt -o filenames 2>/dev/null; {} -d", add)
+        }
+        Hint::Shell(ShellComp::Dir { mask: Some(m) }) => {
+            format!("compopt -o filenames 2>/dev/null; {}", masked("-d", m))
+        }
+        Hint::Shell(ShellComp::Raw { bash, .. }) => bash.to_owned(),
+        Hint::Dynamic => format!("_bpaf_{}_dynamic", app),
+        Hint::OneOf(values) => {
+            let values = values.iter().map(|v| v.0).collect::<Vec<_>>().join(" ");
+            format!("{} -W {}", add, Shell(&values))
+        }
+    }
+}
+
+fn zsh_hint(hint: Hint) -> String {
+    match hint {
+        Hint::Nothing | Hint::Shell(ShellComp::Nothing) => String::new(),
+        Hint::Shell(ShellComp::File { mask: None }) => "_files".to_owned(),
+        Hint::Shell(ShellComp::File { mask: Some(m) }) => format!("_files -g {}", Shell(m)),
+        Hint::Shell(ShellComp::Dir { mask: None }) => "_files -/".to_owned(),
+        Hint::Shell(ShellComp::Dir { mask: Some(m) }) => format!("_files -/ -g {}", Shell(m)),
+        Hint::Shell(ShellComp::Raw { zsh, .. }) => zsh.to_owned(),
+        Hint::Dynamic => {
+            r#"source <(${words[1]} --bpaf-complete-rev=7 "${(@)words[2,CURRENT]}")"#.to_owned()
+        }
+        Hint::OneOf(values) => {
+            let values = values
+                .iter()
+                .map(|(value, help)| {
+                    let value = value.replace(':', "\\:");
+                    let value = match help {
+                        Some(help) => format!("{}:{}", value, help),
+                        None => value,
+                    };
+                    Shell(&value).to_string()
+                })
+                .collect::<Vec<_>>();
+            format!("values=({}); _describe value values", values.join(" "))
+        }
+    }
+}
+
+/// Regular expression that matches file names ending with a shell mask
+pub(crate) fn mask_regex(mask: &str) -> String {
+    let mut res = String::from("(^|/)");
+    for c in mask.chars() {
+        match c {
+            '*' => res.push_str("[^/]*"),
+            '?' => res.push_str("[^/]"),
+            '(' | ')' | '|' => res.push(c),
+            c if c.is_ascii_alphanumeric() => res.push(c),
+            c => {
+                res.push('\\');
+                res.push(c);
+            }
+        }
+    }
+    res.push('$');
+    res
+}
+
+fn fish_hint(hint: Hint, indent: &str) -> String {
+    let files = |dirs: bool, files: bool, mask: Option<&str>| {
+        let test = match mask {
+            Some(mask) => format!(" if string match -qr -- {} $f", Fish(&mask_regex(mask))),
+            None => String::new(),
+        };
+        let mut res = String::new();
+        writeln!(res, "for f in \"$cur\"*").unwrap();
+        if dirs {
+            // with no mask for directories all of them are included
+            writeln!(res, "{}    if test -d $f", indent).unwrap();
+            writeln!(res, "{}        echo $pre$f/", indent).unwrap();
+            if files {
+                writeln!(res, "{}    else{}", indent, test).unwrap();
+                writeln!(res, "{}        echo $pre$f", indent).unwrap();
+            }
+        } else {
+            writeln!(res, "{}    if test -d $f; and{}", indent, test).unwrap();
+            writeln!(res, "{}        echo $pre$f/", indent).unwrap();
+        }
+        writeln!(res, "{}    end", indent).unwrap();
+        write!(res, "{}end", indent).unwrap();
+        res
+    };
+    match hint {
+        Hint::Nothing | Hint::Shell(ShellComp::Nothing) => String::new(),
+        Hint::Shell(ShellComp::File { mask }) => files(true, true, mask),
+        Hint::Shell(ShellComp::Dir { mask: None }) => files(true, false, None),
+        Hint::Shell(ShellComp::Dir { mask: Some(m) }) => files(false, false, Some(m)),
+        Hint::Shell(ShellComp::Raw { fish, .. }) => fish.to_owned(),
+        Hint::Dynamic => {
+            "$words[1] --bpaf-complete-rev=1 $words[2..-1] \"$pre$cur\" | string match -rv '^(\\t.*)?$'"
+                .to_owned()
+        }
+        Hint::OneOf(values) => values
+            .iter()
+            .map(|(value, help)| match help {
+                Some(help) => format!("printf '%s\\t%s\\n' $pre{} {}", Fish(value), Fish(help)),
+                None => format!("echo $pre{}", Fish(value)),
+            })
+            .collect::<Vec<_>>()
+            .join(&format!("\n{}", indent)),
+    }
+}
+
+impl<T> OptionParser<T> {
+    /// Render static bash completion script
+    ///
+    /// Script contains all the flags, arguments and commands of the parser and calls the app
+    /// only to complete values produced by [`complete`](crate::Parser::complete) functions.
+    /// Unlike dynamic completion script you need to regenerate it when the parser changes.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v').long("verbose").switch().to_options();
+    /// let script = parser.render_bash_completion("app");
+    /// assert!(script.contains("complete -F _bpaf_static_app 'app'"));
+    /// ```
+    pub fn render_bash_completion(&self, app: impl AsRef<str>) -> String {
+        let app = app.as_ref();
+        let id = ident(app);
+        let levels = levels(self, app);
+        let mut res = String::new();
+        let w = &mut res;
+
+        writeln!(w, "_bpaf_{}_add() {{", id).unwrap();
+        writeln!(
+            w,
+            "    mapfile -t -O \"${{#COMPREPLY[@]}}\" COMPREPLY < <(compgen \"$@\" -- \"$cur\")"
+        )
+        .unwrap();
+        writeln!(w, "}}").unwrap();
+        writeln!(w, "_bpaf_{}_dynamic() {{", id).unwrap();
+        // bash splits "--key=value" into "--key", "=" and "value", app needs them glued back
+        writeln!(w, "    local -a args=()").unwrap();
+        writeln!(w, "    local w glue=''").unwrap();
+        writeln!(w, "    for w in \"${{COMP_WORDS[@]:1:COMP_CWORD}}\"; do").unwrap();
+        writeln!(
+            w,
+            "        if [[ ($w == = || -n $glue) && ${{#args[@]}} -gt 0 ]]; then"
+        )
+        .unwrap();
+        writeln!(w, "            args[${{#args[@]}} - 1]+=\"$w\"").unwrap();
+        writeln!(w, "        else").unwrap();
+        writeln!(w, "            args+=(\"$w\")").unwrap();
+        writeln!(w, "        fi").unwrap();
+        writeln!(w, "        [[ $w == = ]] && glue=1 || glue=''").unwrap();
+        writeln!(w, "    done").unwrap();
+        writeln!(
+            w,
+            "    source <(\"${{COMP_WORDS[0]}}\" --bpaf-complete-rev=8 \"${{args[@]}}\")"
+        )
+        .unwrap();
+        writeln!(w, "}}").unwrap();
+        writeln!(w, "_bpaf_static_{}() {{", id).unwrap();
+        writeln!(
+            w,
+            "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" cmd={} arg='' pos=0 i w",
+            Shell(app)
+        )
+        .unwrap();
+        writeln!(w, "    COMPREPLY=()").unwrap();
+        writeln!(w, "    for ((i = 1; i < COMP_CWORD; i++)); do").unwrap();
+        writeln!(w, "        w=\"${{COMP_WORDS[i]}}\"").unwrap();
+        writeln!(w, "        if [[ -n $arg ]]; then").unwrap();
+        // "=" from "--key=value" goes between the name and the value
+        writeln!(w, "            [[ $w == = ]] || arg=''").unwrap();
+        writeln!(w, "            continue").unwrap();
+        writeln!(w, "        fi").unwrap();
+        writeln!(w, "        case \"$cmd:$w\" in").unwrap();
+        for level in &levels {
+            for cmd in &level.commands {
+                let path = format!("{} {}", level.path, cmd.names[0]);
+                writeln!(
+                    w,
+                    "            {}) cmd={}; pos=0 ;;",
+                    patterns(&level.path, &cmd.names, "|", false),
+                    Shell(&path)
+                )
+                .unwrap();
+            }
+            for (named, key, _) in arguments(level) {
+                writeln!(
+                    w,
+                    "            {}) arg={} ;;",
+                    patterns(&level.path, &named.names, "|", false),
+                    Shell(&key)
+                )
+                .unwrap();
+            }
+        }
+        writeln!(w, "            *) [[ $w == -* ]] || ((pos++)) ;;").unwrap();
+        writeln!(w, "        esac").unwrap();
+        writeln!(w, "    done").unwrap();
+        writeln!(w, "    [[ -n $arg && $cur == = ]] && cur=''").unwrap();
+
+        writeln!(w, "    case \"$arg\" in").unwrap();
+        for level in &levels {
+            for (_, key, hint) in arguments(level) {
+                let hint = bash_hint(hint, &id);
+                let sep = if hint.is_empty() { "" } else { "; " };
+                writeln!(w, "        {}) {}{}return ;;", Shell(&key), hint, sep).unwrap();
+            }
+        }
+        writeln!(w, "    esac").unwrap();
+
+        writeln!(w, "    case \"$cmd\" in").unwrap();
+        for level in &levels {
+            writeln!(w, "        {})", Shell(&level.path)).unwrap();
+            let flags = level
+                .named
+                .iter()
+                .flat_map(|n| n.names.first())
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(w, "            if [[ $cur == -* ]]; then").unwrap();
+            writeln!(w, "                _bpaf_{}_add -W {}", id, Shell(&flags)).unwrap();
+            writeln!(w, "                return").unwrap();
+            writeln!(w, "            fi").unwrap();
+            if !level.commands.is_empty() {
+                let commands = level
+                    .commands
+                    .iter()
+                    .map(|c| c.names[0].as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                writeln!(w, "            _bpaf_{}_add -W {}", id, Shell(&commands)).unwrap();
+            }
+            let branches = positional_branches(level);
+            if !branches.is_empty() {
+                writeln!(w, "            case \"$pos\" in").unwrap();
+                for (pat, hint) in branches {
+                    writeln!(w, "                {}) {} ;;", pat, bash_hint(hint, &id)).unwrap();
+                }
+                writeln!(w, "            esac").unwrap();
+            }
+            writeln!(w, "            ;;").unwrap();
+        }
+        writeln!(w, "    esac").unwrap();
+        writeln!(w, "}}").unwrap();
+        writeln!(w, "complete -F _bpaf_static_{} {}", id, Shell(app)).unwrap();
+        res
+    }
+
+    /// Render static zsh completion script
+    ///
+    /// See [`render_bash_completion`](OptionParser::render_bash_completion) for details.
+    pub fn render_zsh_completion(&self, app: impl AsRef<str>) -> String {
+        fn describe(name: &str, help: &Option<String>) -> String {
+            let name = name.replace(':', "\\:");
+            match help {
+                Some(help) => format!("{}:{}", name, help),
+                None => name,
+            }
+        }
+
+        let app = app.as_ref();
+        let levels = levels(self, app);
+        let mut res = String::new();
+        let w = &mut res;
+
+        writeln!(w, "#compdef {}", app).unwrap();
+        writeln!(
+            w,
+            "local cur=${{words[CURRENT]}} cmd={} arg='' eq='' pos=0 i w",
+            Shell(app)
+        )
+        .unwrap();
+        writeln!(w, "local -a flags commands values").unwrap();
+        writeln!(w, "for ((i = 2; i < CURRENT; i++)); do").unwrap();
+        writeln!(w, "    w=${{words[i]}}").unwrap();
+        writeln!(w, "    if [[ -n $arg ]]; then").unwrap();
+        writeln!(w, "        arg=''").unwrap();
+        writeln!(w, "        continue").unwrap();
+        writeln!(w, "    fi").unwrap();
+        writeln!(w, "    case \"$cmd:$w\" in").unwrap();
+        for level in &levels {
+            for cmd in &level.commands {
+                let path = format!("{} {}", level.path, cmd.names[0]);
+                writeln!(
+                    w,
+                    "        {}) cmd={}; pos=0 ;;",
+                    patterns(&level.path, &cmd.names, "|", false),
+                    Shell(&path)
+                )
+                .unwrap();
+            }
+            for (named, key, _) in arguments(level) {
+                writeln!(
+                    w,
+                    "        {}) arg={} ;;",
+                    patterns(&level.path, &named.names, "|", false),
+                    Shell(&key)
+                )
+                .unwrap();
+            }
+        }
+        writeln!(w, "        *) [[ $w == -* ]] || ((pos++)) ;;").unwrap();
+        writeln!(w, "    esac").unwrap();
+        writeln!(w, "done").unwrap();
+        // value in "--key=value" form is completed by the argument's hint
+        writeln!(w, "if [[ -z $arg && $cur == --*=* ]]; then").unwrap();
+        writeln!(w, "    case \"$cmd:${{cur%%=*}}\" in").unwrap();
+        for level in &levels {
+            for (named, key, _) in arguments(level) {
+                let longs = long_names(named);
+                if longs.is_empty() {
+                    continue;
+                }
+                writeln!(
+                    w,
+                    "        {}) arg={} ;;",
+                    patterns(&level.path, &longs, "|", false),
+                    Shell(&key)
+                )
+                .unwrap();
+            }
+        }
+        writeln!(w, "    esac").unwrap();
+        writeln!(w, "    [[ -n $arg ]] && eq=1").unwrap();
+        writeln!(w, "fi").unwrap();
+
+        writeln!(w, "case $arg in").unwrap();
+        for level in &levels {
+            for (_, key, hint) in arguments(level) {
+                // dynamic completion gets the whole word and deals with the prefix itself
+                let strip = match hint {
+                    Hint::Nothing | Hint::Shell(ShellComp::Nothing) | Hint::Dynamic => "",
+                    Hint::Shell(_) | Hint::OneOf(_) => "[[ -n $eq ]] && compset -P 1 '*='; ",
+                };
+                let hint = zsh_hint(hint);
+                let sep = if hint.is_empty() { "" } else { "; " };
+                writeln!(w, "    {}) {}{}{}return ;;", Shell(&key), strip, hint, sep).unwrap();
+            }
+        }
+        writeln!(w, "esac").unwrap();
+
+        writeln!(w, "case $cmd in").unwrap();
+        for level in &levels {
+            writeln!(w, "    {})", Shell(&level.path)).unwrap();
+            write!(w, "        flags=(").unwrap();
+            for (ix, named) in level.named.iter().enumerate() {
+                if ix > 0 {
+                    w.push(' ');
+                }
+                write!(w, "{}", Shell(&describe(&named.names[0], &named.help))).unwrap();
+            }
+            writeln!(w, ")").unwrap();
+            writeln!(w, "        if [[ $cur == -* ]]; then").unwrap();
+            writeln!(w, "            _describe option flags").unwrap();
+            writeln!(w, "            return").unwrap();
+            writeln!(w, "        fi").unwrap();
+            if !level.commands.is_empty() {
+                write!(w, "        commands=(").unwrap();
+                for (ix, cmd) in level.commands.iter().enumerate() {
+                    if ix > 0 {
+                        w.push(' ');
+                    }
+                    write!(w, "{}", Shell(&describe(&cmd.names[0], &cmd.help))).unwrap();
+                }
+                writeln!(w, ")").unwrap();
+                writeln!(w, "        _describe command commands").unwrap();
+            }
+            let branches = positional_branches(level);
+            if !branches.is_empty() {
+                writeln!(w, "        case $pos in").unwrap();
+                for (pat, hint) in branches {
+                    writeln!(w, "            {}) {} ;;", pat, zsh_hint(hint)).unwrap();
+                }
+                writeln!(w, "        esac").unwrap();
+            }
+            writeln!(w, "        ;;").unwrap();
+        }
+        writeln!(w, "esac").unwrap();
+        res
+    }
+
+    /// Render static fish completion script
+    ///
+    /// See [`render_bash_completion`](OptionParser::render_bash_completion) for details.
+    pub fn render_fish_completion(&self, app: impl AsRef<str>) -> String {
+        fn print(w: &mut String, indent: &str, name: &str, help: &Option<String>) {
+            match help {
+                Some(help) => writeln!(
+                    w,
+                    "{}printf '%s\\t%s\\n' {} {}",
+                    indent,
+                    Fish(name),
+                    Fish(help)
+                ),
+                None => writeln!(w, "{}echo {}", indent, Fish(name)),
+            }
+            .unwrap();
+        }
+
+        let app = app.as_ref();
+        let id = ident(app);
+        let levels = levels(self, app);
+        let mut res = String::new();
+        let w = &mut res;
+
+        writeln!(w, "function __bpaf_static_{}", id).unwrap();
+        writeln!(
+            w,
+            "    set -l words (commandline --tokenize --cut-at-cursor --current-process)"
+        )
+        .unwrap();
+        writeln!(w, "    set -l cur (commandline --current-token)").unwrap();
+        writeln!(w, "    set -l cmd {}", Fish(app)).unwrap();
+        writeln!(w, "    set -l arg ''").unwrap();
+        writeln!(w, "    set -l pos 0").unwrap();
+        writeln!(w, "    set -l pre ''").unwrap();
+        writeln!(w, "    for w in $words[2..-1]").unwrap();
+        writeln!(w, "        if test -n \"$arg\"").unwrap();
+        writeln!(w, "            set arg ''").unwrap();
+        writeln!(w, "            continue").unwrap();
+        writeln!(w, "        end").unwrap();
+        writeln!(w, "        switch \"$cmd:$w\"").unwrap();
+        for level in &levels {
+            for cmd in &level.commands {
+                let path = format!("{} {}", level.path, cmd.names[0]);
+                writeln!(
+                    w,
+                    "            case {}",
+                    patterns(&level.path, &cmd.names, " ", true)
+                )
+                .unwrap();
+                writeln!(w, "                set cmd {}", Fish(&path)).unwrap();
+                writeln!(w, "                set pos 0").unwrap();
+            }
+            for (named, key, _) in arguments(level) {
+                writeln!(
+                    w,
+                    "            case {}",
+                    patterns(&level.path, &named.names, " ", true)
+                )
+                .unwrap();
+                writeln!(w, "                set arg {}", Fish(&key)).unwrap();
+            }
+        }
+        writeln!(w, "            case '*'").unwrap();
+        writeln!(
+            w,
+            "                string match -q -- '-*' $w; or set pos (math $pos + 1)"
+        )
+        .unwrap();
+        writeln!(w, "        end").unwrap();
+        writeln!(w, "    end").unwrap();
+        // value in "--key=value" form is completed by the argument's hint, file names
+        // get the "--key=" part back
+        writeln!(
+            w,
+            "    if test -z \"$arg\"; and string match -q -- '--*=*' \"$cur\""
+        )
+        .unwrap();
+        writeln!(w, "        set -l kv (string split -m 1 -- = \"$cur\")").unwrap();
+        writeln!(w, "        switch \"$cmd:$kv[1]\"").unwrap();
+        for level in &levels {
+            for (named, key, _) in arguments(level) {
+                let longs = long_names(named);
+                if longs.is_empty() {
+                    continue;
+                }
+                writeln!(
+                    w,
+                    "            case {}",
+                    patterns(&level.path, &longs, " ", true)
+                )
+                .unwrap();
+                writeln!(w, "                set arg {}", Fish(&key)).unwrap();
+            }
+        }
+        writeln!(w, "        end").unwrap();
+        writeln!(w, "        if test -n \"$arg\"").unwrap();
+        writeln!(w, "            set pre \"$kv[1]=\"").unwrap();
+        writeln!(w, "            set cur \"$kv[2]\"").unwrap();
+        writeln!(w, "        end").unwrap();
+        writeln!(w, "    end").unwrap();
+
+        writeln!(w, "    switch \"$arg\"").unwrap();
+        for level in &levels {
+            for (_, key, hint) in arguments(level) {
+                writeln!(w, "        case {}", Fish(&key)).unwrap();
+                let hint = fish_hint(hint, "            ");
+                if !hint.is_empty() {
+                    writeln!(w, "            {}", hint).unwrap();
+                }
+                writeln!(w, "            return").unwrap();
+            }
+        }
+        writeln!(w, "    end").unwrap();
+
+        writeln!(w, "    switch \"$cmd\"").unwrap();
+        for level in &levels {
+            writeln!(w, "        case {}", Fish(&level.path)).unwrap();
+            writeln!(w, "            if string match -q -- '-*' \"$cur\"").unwrap();
+            for named in &level.named {
+                print(w, "                ", &named.names[0], &named.help);
+            }
+            writeln!(w, "                return").unwrap();
+            writeln!(w, "            end").unwrap();
+            for cmd in &level.commands {
+                print(w, "            ", &cmd.names[0], &cmd.help);
+            }
+            let branches = positional_branches(level);
+            if !branches.is_empty() {
+                writeln!(w, "            switch $pos").unwrap();
+                for (pat, hint) in branches {
+                    writeln!(w, "                case {}", Fish(&pat)).unwrap();
+                    writeln!(
+                        w,
+                        "                    {}",
+                        fish_hint(hint, "                    ")
+                    )
+                    .unwrap();
+                }
+                writeln!(w, "            end").unwrap();
+            }
+        }
+        writeln!(w, "    end").unwrap();
+        writeln!(w, "end").unwrap();
+        writeln!(
+            w,
+            "complete -c {} -f -a '(__bpaf_static_{})'",
+            Fish(app),
+            id
+        )
+        .unwrap();
+        res
+    }
+}
@@ -50,7 +50,7 @@
 //!
 
 #[doc(inline)]
-pub use crate::buffer::{Doc, MetaInfo, Style};
+pub use crate::buffer::{ColorChoice, Doc, MetaInfo, Style, Theme};
 
 #[doc(inline)]
 #[cfg(feature = "docgen")]
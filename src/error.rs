@@ -2,7 +2,7 @@ use std::ops::Range;
 
 use crate::{
     args::{Arg, State},
-    buffer::{Block, Color, Doc, Style, Token},
+    buffer::{Block, Doc, Style, Theme, Token},
     item::{Item, ShortLong},
     meta_help::Metavar,
     meta_youmean::{Suggestion, Variant},
@@ -158,8 +158,9 @@ impl Message {
 /// shell completion mechanism relies on that. In both cases application should exit with error
 /// code of 0. `Stderr` variant indicates a genuinly parsing error which should be printed to
 /// stderr or a logging framework of your choice as an error and the app should exit with error
-/// code of 1. [`ParseFailure::exit_code`] is a helper method that performs printing and produces
-/// the exit code to use.
+/// code of 1. Each variant carries an [`ExitStatus`] with the [`FailureKind`] and the exit code to
+/// use, code can be changed with [`OptionParser::exit_code`](crate::OptionParser::exit_code).
+/// [`ParseFailure::exit_code`] and [`ParseFailure::kind`] are helpers to access them.
 ///
 /// For purposes of for unit testing for user parsers, you can consume it with
 /// [`ParseFailure::unwrap_stdout`] and [`ParseFailure::unwrap_stdout`] - both of which produce a
@@ -168,12 +169,59 @@ impl Message {
 #[derive(Clone, Debug)]
 pub enum ParseFailure {
     /// Print this to stdout and exit with success code
-    Stdout(Doc, bool),
+    Stdout(Doc, bool, ExitStatus),
     /// This also goes to stdout with exit code of 0,
     /// this cannot be Doc because completion needs more control about rendering
-    Completion(String),
+    Completion(String, ExitStatus),
     /// Print this to stderr and exit with failure code
-    Stderr(Doc),
+    Stderr(Doc, ExitStatus),
+}
+
+/// Kind of [`ParseFailure`] and exit code to use for it
+///
+/// `code` is 0 for help, version and completion and 1 for errors unless changed with
+/// [`OptionParser::exit_code`](crate::OptionParser::exit_code)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ExitStatus {
+    /// Kind of the failure
+    pub kind: FailureKind,
+    /// Exit code to use
+    pub code: i32,
+}
+
+impl From<FailureKind> for ExitStatus {
+    fn from(kind: FailureKind) -> Self {
+        ExitStatus {
+            kind,
+            code: kind.default_exit_code(),
+        }
+    }
+}
+
+/// Kind of [`ParseFailure`], used to pick an exit code
+///
+/// See [`OptionParser::exit_code`](crate::OptionParser::exit_code)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FailureKind {
+    /// Command line contains unexpected, conflicting or missing items
+    Usage,
+    /// One of the values failed to parse or failed a [`guard`](crate::Parser::guard) check
+    Validation,
+    /// User asked for help
+    Help,
+    /// User asked for version information
+    Version,
+    /// Shell asked for completion
+    Completion,
+}
+
+impl FailureKind {
+    pub(crate) fn default_exit_code(self) -> i32 {
+        match self {
+            FailureKind::Usage | FailureKind::Validation => 1,
+            FailureKind::Help | FailureKind::Version | FailureKind::Completion => 0,
+        }
+    }
 }
 
 impl ParseFailure {
@@ -186,7 +234,7 @@ impl ParseFailure {
     #[track_caller]
     pub fn unwrap_stderr(self) -> String {
         match self {
-            Self::Stderr(err) => err.monochrome(true),
+            Self::Stderr(err, _) => err.monochrome(true),
             Self::Completion(..) | Self::Stdout(..) => panic!("not an stderr: {:?}", self),
         }
     }
@@ -200,19 +248,48 @@ impl ParseFailure {
     #[track_caller]
     pub fn unwrap_stdout(self) -> String {
         match self {
-            Self::Stdout(err, full) => err.monochrome(full),
-            Self::Completion(s) => s,
+            Self::Stdout(err, full, _) => err.monochrome(full),
+            Self::Completion(s, _) => s,
             Self::Stderr(..) => panic!("not an stdout: {:?}", self),
         }
     }
 
     /// Returns the exit code for the failure
+    ///
+    /// 0 for help, version and completion, 1 for errors, unless changed with
+    /// [`OptionParser::exit_code`](crate::OptionParser::exit_code)
     #[allow(clippy::must_use_candidate)]
     pub fn exit_code(self) -> i32 {
+        self.status().code
+    }
+
+    /// Returns the kind of the failure
+    #[must_use]
+    pub fn kind(&self) -> FailureKind {
+        self.status().kind
+    }
+
+    /// Returns the kind of the failure along with the exit code
+    #[must_use]
+    pub fn status(&self) -> ExitStatus {
         match self {
-            Self::Stdout(..) | Self::Completion(..) => 0,
-            Self::Stderr(..) => 1,
+            Self::Stdout(_, _, status) | Self::Completion(_, status) | Self::Stderr(_, status) => {
+                *status
+            }
+        }
+    }
+
+    /// Replace the exit code with one configured for this kind of failure, if any
+    pub(crate) fn with_exit_codes(mut self, codes: &[(FailureKind, i32)]) -> Self {
+        let status = match &mut self {
+            Self::Stdout(_, _, status) | Self::Completion(_, status) | Self::Stderr(_, status) => {
+                status
+            }
+        };
+        if let Some((_, code)) = codes.iter().rev().find(|(k, _)| *k == status.kind) {
+            status.code = *code;
         }
+        self
     }
 
     #[doc(hidden)]
@@ -223,29 +300,22 @@ impl ParseFailure {
 
     /// Prints a message to `stdout` or `stderr` appropriate to the failure.
     pub fn print_message(&self, max_width: usize) {
-        let color = Color::default();
+        self.print_themed(max_width, None);
+    }
+
+    pub(crate) fn print_themed(&self, max_width: usize, theme: Option<Theme>) {
+        let theme = Theme::resolve(theme);
         match self {
-            ParseFailure::Stdout(msg, full) => {
-                println!("{}", msg.render_console(*full, color, max_width));
+            ParseFailure::Stdout(msg, full, _) => {
+                println!("{}", msg.render_console(*full, &theme, max_width));
             }
-            ParseFailure::Completion(s) => {
+            ParseFailure::Completion(s, _) => {
                 print!("{}", s);
             }
-            ParseFailure::Stderr(msg) => {
-                #[allow(unused_mut)]
-                let mut error;
-                #[cfg(not(feature = "color"))]
-                {
-                    error = "Error: ";
-                }
-
-                #[cfg(feature = "color")]
-                {
-                    error = String::new();
-                    color.push_str(Style::Invalid, &mut error, "Error: ");
-                }
-
-                eprintln!("{}{}", error, msg.render_console(true, color, max_width));
+            ParseFailure::Stderr(msg, _) => {
+                let mut error = String::new();
+                theme.push_str(Style::Invalid, &mut error, "Error: ");
+                eprintln!("{}{}", error, msg.render_console(true, &theme, max_width));
             }
         }
     }
@@ -296,6 +366,10 @@ impl Message {
             _ => {}
         }
 
+        let validation = matches!(
+            self,
+            Message::ParseFailed(..) | Message::GuardFailed(..) | Message::PureFailed(..)
+        );
         let mut doc = Doc::default();
         match self {
             // already rendered
@@ -482,7 +556,7 @@ impl Message {
                         doc.token(Token::BlockStart(Block::TermRef));
 
                         match v {
-                            Variant::CommandLong(name) => doc.literal(name),
+                            Variant::CommandLong(name) => doc.literal(&name),
                             Variant::Flag(ShortLong::Long(l) | ShortLong::Both(_, l)) => {
                                 doc.literal("--");
                                 doc.literal(l);
@@ -613,7 +687,12 @@ impl Message {
             }
         };
 
-        ParseFailure::Stderr(doc)
+        let kind = if validation {
+            FailureKind::Validation
+        } else {
+            FailureKind::Usage
+        };
+        ParseFailure::Stderr(doc, kind.into())
     }
 }
 
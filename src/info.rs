@@ -1,11 +1,13 @@
 //! Help message generation and rendering
 
 use crate::{
-    args::{Args, State},
+    args::{Arg, Args, State},
+    doc::Theme,
     error::Message,
-    meta_help::render_help,
+    item::Item,
+    meta_help::{render_help, render_help_search},
     parsers::NamedArg,
-    short, Doc, Error, Meta, ParseFailure, Parser,
+    short, Doc, Error, FailureKind, Meta, ParseFailure, Parser,
 };
 
 /// Information about the parser
@@ -24,10 +26,35 @@ pub struct Info {
     pub footer: Option<Doc>,
     /// Custom usage field, see [`usage`][Info::usage]
     pub usage: Option<Doc>,
+    /// Usage examples, see [`example`][OptionParser::example]
+    pub examples: Vec<(&'static str, Doc)>,
+    /// Exit status table, see [`exit_status`][OptionParser::exit_status]
+    pub exit_statuses: Vec<(i32, Doc)>,
+    /// Files used by the app, see [`file`][OptionParser::file]
+    pub files: Vec<(&'static str, Doc)>,
+    /// Authors, see [`authors`][OptionParser::authors]
+    pub authors: Option<Doc>,
+    /// Bug report URL, see [`bug_url`][OptionParser::bug_url]
+    pub bug_url: Option<&'static str>,
     pub help_arg: NamedArg,
     pub version_arg: NamedArg,
     pub help_if_no_args: bool,
+    /// Accept `help <command>`, see [`help_command`][OptionParser::help_command]
+    pub help_command: bool,
+    /// Accept `--help <term>`, see [`searchable_help`][OptionParser::searchable_help]
+    pub searchable_help: bool,
     pub max_width: usize,
+    /// Custom color theme, see [`theme`][OptionParser::theme]
+    pub theme: Option<Theme>,
+    /// Custom exit codes, see [`exit_code`][OptionParser::exit_code]
+    pub exit_codes: Vec<(FailureKind, i32)>,
+    /// Completion matcher, see [`completion_matcher`][OptionParser::completion_matcher]
+    #[cfg(feature = "autocomplete")]
+    pub matcher: crate::CompletionMatcher,
+    /// Environment variable for completion protocol, see
+    /// [`completion_env`][OptionParser::completion_env]
+    #[cfg(feature = "autocomplete")]
+    pub completion_env: Option<&'static str>,
 }
 
 impl Default for Info {
@@ -38,12 +65,25 @@ impl Default for Info {
             header: None,
             footer: None,
             usage: None,
+            examples: Vec::new(),
+            exit_statuses: Vec::new(),
+            files: Vec::new(),
+            authors: None,
+            bug_url: None,
             help_arg: short('h').long("help").help("Prints help information"),
             version_arg: short('V')
                 .long("version")
                 .help("Prints version information"),
             help_if_no_args: false,
+            help_command: false,
+            searchable_help: false,
             max_width: 100,
+            theme: None,
+            exit_codes: Vec::new(),
+            #[cfg(feature = "autocomplete")]
+            matcher: crate::CompletionMatcher::Prefix,
+            #[cfg(feature = "autocomplete")]
+            completion_env: None,
         }
     }
 }
@@ -90,7 +130,7 @@ impl<T> OptionParser<T> {
         match self.run_inner(Args::current_args()) {
             Ok(t) => t,
             Err(err) => {
-                err.print_message(self.info.max_width);
+                err.print_themed(self.info.max_width, self.info.theme);
                 std::process::exit(err.exit_code())
             }
         }
@@ -119,15 +159,15 @@ impl<T> OptionParser<T> {
     /// fn main() {
     ///     let verbosity: Option<usize> = match verbosity().try_run() {
     ///         Ok(v) => Some(v),
-    ///         Err(ParseFailure::Stdout(buf, full)) => {
+    ///         Err(ParseFailure::Stdout(buf, full, _)) => {
     ///             print!("{}", buf.monochrome(full));
     ///             None
     ///         }
-    ///         Err(ParseFailure::Completion(msg)) => {
+    ///         Err(ParseFailure::Completion(msg, _)) => {
     ///             print!("{}", msg);
     ///             None
     ///         }
-    ///         Err(ParseFailure::Stderr(buf)) => {
+    ///         Err(ParseFailure::Stderr(buf, _)) => {
     ///             eprintln!("{}", buf.monochrome(true));
     ///             None
     ///         }
@@ -207,9 +247,22 @@ impl<T> OptionParser<T> {
         short_flags.extend(&self.info.help_arg.short);
         short_flags.extend(&self.info.version_arg.short);
         let args = args.into();
+        #[cfg(feature = "autocomplete")]
+        let args = match self.info.completion_env {
+            Some(var) => crate::complete_run::env_protocol(var, args).map_err(|comp| {
+                ParseFailure::Completion(comp, FailureKind::Completion.into())
+                    .with_exit_codes(&self.info.exit_codes)
+            })?,
+            None => args,
+        };
         let mut err = None;
         let mut state = State::construct(args, &short_flags, &short_args, &mut err);
 
+        #[cfg(feature = "autocomplete")]
+        if let Some(comp) = state.comp_mut() {
+            comp.matcher = self.info.matcher;
+        }
+
         // this only handles disambiguation failure in construct
         if let Some(msg) = err {
             #[cfg(feature = "autocomplete")]
@@ -219,11 +272,14 @@ impl<T> OptionParser<T> {
             let check_disambiguation = false;
 
             if check_disambiguation {
-                return Err(msg.render(&state, &self.inner.meta()));
+                return Err(msg
+                    .render(&state, &self.inner.meta())
+                    .with_exit_codes(&self.info.exit_codes));
             }
         }
 
         self.run_subparser(&mut state)
+            .map_err(|failure| failure.with_exit_codes(&self.info.exit_codes))
     }
 
     /// Run subparser, implementation detail
@@ -248,16 +304,41 @@ impl<T> OptionParser<T> {
                 &self.info.meta(),
                 true,
             );
-            return Err(ParseFailure::Stdout(buffer, false));
+            return Err(ParseFailure::Stdout(
+                buffer,
+                false,
+                FailureKind::Help.into(),
+            ));
         };
 
+        if let Some(buffer) = self.info.help_search(args, &self.inner.meta()) {
+            return Err(ParseFailure::Stdout(
+                buffer,
+                false,
+                FailureKind::Help.into(),
+            ));
+        }
+
+        if self.info.help_command && args.take_cmd("help") {
+            return Err(help_command(args, &self.inner.meta(), &self.info));
+        }
+        #[cfg(feature = "autocomplete")]
+        let complete_help = self.info.help_command && args.len() == 1;
+
         let res = self.inner.eval(args);
         if let Err(Error(Message::ParseFailure(failure))) = res {
             return Err(failure);
         }
         #[cfg(feature = "autocomplete")]
+        if complete_help {
+            args.push_command("help", None, &Some(Doc::from(HELP_COMMAND)), None);
+        }
+        #[cfg(feature = "autocomplete")]
         if let Some(comp) = args.check_complete() {
-            return Err(ParseFailure::Completion(comp));
+            return Err(ParseFailure::Completion(
+                comp,
+                FailureKind::Completion.into(),
+            ));
         }
 
         let err = match res {
@@ -273,17 +354,16 @@ impl<T> OptionParser<T> {
 
         // handle --help and --version messages
         if let Ok(extra) = self.info.eval(args) {
-            let mut detailed = false;
-            let buffer = match extra {
-                ExtraParams::Help(d) => {
-                    detailed = d;
-                    render_help(
+            let failure = match extra {
+                ExtraParams::Help(detailed) => {
+                    let buffer = render_help(
                         &args.path,
                         &self.info,
                         &self.inner.meta(),
                         &self.info.meta(),
                         true,
-                    )
+                    );
+                    ParseFailure::Stdout(buffer, detailed, FailureKind::Help.into())
                 }
                 ExtraParams::Version(v) => {
                     use crate::buffer::{Block, Token};
@@ -292,10 +372,10 @@ impl<T> OptionParser<T> {
                     buffer.text("Version: ");
                     buffer.doc(&v);
                     buffer.token(Token::BlockEnd(Block::Block));
-                    buffer
+                    ParseFailure::Stdout(buffer, false, FailureKind::Version.into())
                 }
             };
-            return Err(ParseFailure::Stdout(buffer, detailed));
+            return Err(failure);
         }
         Err(err.render(args, &self.inner.meta()))
     }
@@ -545,6 +625,94 @@ impl<T> OptionParser<T> {
         self
     }
 
+    /// Add a usage example: a command line and an explanation of what it does
+    ///
+    /// Examples are listed in `--help` output, markdown and html documentation and in the
+    /// `EXAMPLES` section of generated manpages. Command line should start with the application
+    /// name followed by the command names for examples attached to a subcommand, words are
+    /// separated by whitespace and can be quoted or escaped the same way as in a POSIX shell.
+    /// [`check_invariants`](OptionParser::check_invariants) runs every example through the
+    /// parser so stale examples fail your tests.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v')
+    ///     .switch()
+    ///     .to_options()
+    ///     .example("app -v", "Run the app with verbose output");
+    /// # drop(parser);
+    /// ```
+    #[must_use]
+    pub fn example<B: Into<Doc>>(mut self, cmdline: &'static str, descr: B) -> Self {
+        self.info.examples.push((cmdline, descr.into()));
+        self
+    }
+
+    /// Document an exit status, rendered in the `EXIT STATUS` section of generated manpages
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v')
+    ///     .switch()
+    ///     .to_options()
+    ///     .exit_status(0, "Success")
+    ///     .exit_status(1, "Invalid command line");
+    /// # drop(parser);
+    /// ```
+    #[must_use]
+    pub fn exit_status<B: Into<Doc>>(mut self, code: i32, descr: B) -> Self {
+        self.info.exit_statuses.push((code, descr.into()));
+        self
+    }
+
+    /// Document a file used by the app, rendered in the `FILES` section of generated manpages
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v')
+    ///     .switch()
+    ///     .to_options()
+    ///     .file("~/.config/app.toml", "User configuration");
+    /// # drop(parser);
+    /// ```
+    #[must_use]
+    pub fn file<B: Into<Doc>>(mut self, path: &'static str, descr: B) -> Self {
+        self.info.files.push((path, descr.into()));
+        self
+    }
+
+    /// Set the authors field, rendered in the `AUTHORS` section of generated manpages
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v')
+    ///     .switch()
+    ///     .to_options()
+    ///     .authors(env!("CARGO_PKG_AUTHORS"));
+    /// # drop(parser);
+    /// ```
+    #[must_use]
+    pub fn authors<B: Into<Doc>>(mut self, authors: B) -> Self {
+        self.info.authors = Some(authors.into());
+        self
+    }
+
+    /// Set the bug report URL, rendered in the `REPORTING BUGS` section of generated manpages
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v')
+    ///     .switch()
+    ///     .to_options()
+    ///     .bug_url("https://github.com/pacak/bpaf/issues");
+    /// # drop(parser);
+    /// ```
+    #[must_use]
+    pub fn bug_url(mut self, url: &'static str) -> Self {
+        self.info.bug_url = Some(url);
+        self
+    }
+
     /// Set custom usage field
     ///
     /// Custom usage field to use instead of one derived by `bpaf`.
@@ -599,6 +767,9 @@ impl<T> OptionParser<T> {
     /// Takes a parameter whether to check for cosmetic invariants or not
     /// (max help width exceeding 120 symbols, etc), currently not in use
     ///
+    /// Also runs every [`example`](OptionParser::example), including ones attached to
+    /// subcommands, through the parser and fails if it produces an error.
+    ///
     /// Best used as part of your test suite:
     /// ```no_run
     /// # use bpaf::*;
@@ -613,7 +784,32 @@ impl<T> OptionParser<T> {
     ///
     /// `check_invariants` indicates problems with panic
     pub fn check_invariants(&self, _cosmetic: bool) {
-        self.inner.meta().positional_invariant_check(true);
+        let meta = self.inner.meta();
+        meta.positional_invariant_check(true);
+
+        let mut examples = Vec::new();
+        collect_examples(&self.info, &meta, &mut examples);
+        // parsers can change global state such as color choice, examples shouldn't
+        let color = crate::doc::ColorChoice::global();
+        for cmdline in examples {
+            let words = match split_shell_words(cmdline) {
+                Ok(words) => words,
+                Err(err) => panic!("Example `{}` is not a valid command line: {}", cmdline, err),
+            };
+            let words = words.iter().skip(1).map(String::as_str).collect::<Vec<_>>();
+            let res = self.run_inner(words.as_slice());
+            color.set_global();
+            match res {
+                Err(err @ ParseFailure::Stderr(..)) => {
+                    panic!(
+                        "Example `{}` fails to parse: {}",
+                        cmdline,
+                        err.unwrap_stderr()
+                    );
+                }
+                Ok(_) | Err(ParseFailure::Stdout(..) | ParseFailure::Completion(..)) => {}
+            }
+        }
     }
 
     /// Customize parser for `--help`
@@ -629,6 +825,173 @@ impl<T> OptionParser<T> {
         self
     }
 
+    /// Accept `help <command>` as an alternative to `<command> --help`
+    ///
+    /// With this option enabled `bpaf` handles `help` subcommand by itself: `app help` prints
+    /// help for the `app` and `app help remote add` prints help for `remote add` nested command
+    /// same as `app remote add --help` would. Typos in command names result in usual suggestions
+    /// and `help` participates in shell completion.
+    ///
+    /// `help` subcommand is recognized only as the first item at this level and takes priority
+    /// over commands and positional items with the same name.
+    ///
+    /// Derive API exposes it as `help_command` annotation next to `options` or `command`.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let add = pure(())
+    ///     .to_options()
+    ///     .descr("Add a remote")
+    ///     .command("add");
+    /// let remote = add
+    ///     .to_options()
+    ///     .descr("Manage remotes")
+    ///     .command("remote");
+    /// let parser = remote.to_options().help_command();
+    ///
+    /// let help = parser
+    ///     .run_inner(&["help", "remote", "add"])
+    ///     .unwrap_err()
+    ///     .unwrap_stdout();
+    /// let expected = parser
+    ///     .run_inner(&["remote", "add", "--help"])
+    ///     .unwrap_err()
+    ///     .unwrap_stdout();
+    /// assert_eq!(help, expected);
+    /// ```
+    #[must_use]
+    pub fn help_command(mut self) -> Self {
+        self.info.help_command = true;
+        self
+    }
+
+    /// Allow `--help` to take an optional search term
+    ///
+    /// Large applications can produce help that spans multiple pages. With this option enabled
+    /// `app --help term` or `app --help=term` renders the usage line and only those options,
+    /// positional items, commands and groups whose names or help mention `term`, ignoring the
+    /// case. Matches from nested commands are listed in separate sections,
+    /// `app cmd --help term` searches inside of `cmd` only.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').long("verbose").help("Produce more output").switch();
+    /// let jobs = short('j').long("jobs").help("Number of jobs to run").argument::<usize>("N");
+    /// let force = long("force").help("Fetch even if output is verbose").switch();
+    /// let fetch = force.to_options().descr("Fetch the sources").command("fetch");
+    /// let parser = construct!(verbose, jobs, fetch).to_options().searchable_help();
+    ///
+    /// let help = parser
+    ///     .run_inner(&["--help", "VERBOSE"])
+    ///     .unwrap_err()
+    ///     .unwrap_stdout();
+    /// let expected = "\
+    /// Usage: [-v] -j=N COMMAND ...
+    ///
+    /// Available options:
+    ///     -v, --verbose  Produce more output
+    ///
+    /// Found in `fetch` command:
+    ///         --force    Fetch even if output is verbose
+    /// ";
+    /// assert_eq!(help, expected);
+    /// ```
+    #[must_use]
+    pub fn searchable_help(mut self) -> Self {
+        self.info.searchable_help = true;
+        self
+    }
+
+    /// Change how shell completion matches partially typed names
+    ///
+    /// By default names of flags, arguments and commands must start with the typed text.
+    /// [`CompletionMatcher::Fuzzy`](crate::CompletionMatcher::Fuzzy) also accepts case
+    /// insensitive prefixes, substrings and subsequences, with the best matches listed first,
+    /// and offers names with small typos when nothing else matches.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let add = pure(()).to_options().descr("Add a remote").command("remote-add");
+    /// let remove = pure(()).to_options().descr("Remove a remote").command("remote-remove");
+    /// let parser = construct!([add, remove])
+    ///     .to_options()
+    ///     .completion_matcher(CompletionMatcher::Fuzzy);
+    ///
+    /// let r = parser
+    ///     .run_inner(Args::from(&["rmv"]).set_comp(0))
+    ///     .unwrap_err()
+    ///     .unwrap_stdout();
+    /// assert_eq!(r, "remote-remove");
+    /// ```
+    #[cfg(feature = "autocomplete")]
+    #[must_use]
+    pub fn completion_matcher(mut self, matcher: crate::CompletionMatcher) -> Self {
+        self.info.matcher = matcher;
+        self
+    }
+
+    /// Accept completion requests from an environment variable
+    ///
+    /// By default shell completion scripts call the app with `--bpaf-complete-rev=N` in front of
+    /// the words to complete, which gets in the way of wrappers such as
+    /// [`cargo_helper`](crate::batteries::cargo_helper) or parsers that take anything with
+    /// [`any`](crate::any). With this option the scripts set `var` to the shell name and
+    /// pass the words as is instead.
+    ///
+    /// Running the app with `var` set to `bash`, `zsh`, `fish`, `elvish`, `powershell` or `nushell`
+    /// and no arguments prints the completion script for that shell:
+    ///
+    /// ```console
+    /// $ COMPLETE=bash your_program >> ~/.bash_completion
+    /// ```
+    ///
+    /// `--bpaf-complete-style-*` flags keep working and produce scripts that use
+    /// `--bpaf-complete-rev`.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v').long("verbose").switch().to_options().completion_env("COMPLETE");
+    /// # drop(parser);
+    /// ```
+    #[cfg(feature = "autocomplete")]
+    #[must_use]
+    pub fn completion_env(mut self, var: &'static str) -> Self {
+        self.info.completion_env = Some(var);
+        self
+    }
+
+    /// Get completion candidates without running a shell
+    ///
+    /// `args` are the words before the cursor without the program name and `cursor_word` is
+    /// the word being completed, empty if cursor is after a space. Candidates are the same
+    /// shell completion would offer, [`Completion::text`](crate::Completion::text) replaces
+    /// `cursor_word` completely.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = long("verbose").help("Be verbose").switch();
+    /// let name = long("name").argument::<String>("NAME");
+    /// let parser = construct!(verbose, name).to_options();
+    ///
+    /// let r = parser.completions(&[], "--v");
+    /// assert_eq!(r.len(), 1);
+    /// assert_eq!(r[0].text, "--verbose");
+    /// assert_eq!(r[0].description.as_deref(), Some("Be verbose"));
+    /// assert_eq!(r[0].kind, CompletionKind::Flag);
+    /// ```
+    #[cfg(feature = "autocomplete")]
+    pub fn completions(&self, args: &[&str], cursor_word: &str) -> Vec<crate::Completion> {
+        let mut words = args.to_vec();
+        words.push(cursor_word);
+        let args = Args::from(words.as_slice()).set_comp(crate::complete_gen::IN_PROCESS_REV);
+        match self.run_inner(args) {
+            Err(ParseFailure::Completion(lines, _)) => {
+                lines.lines().filter_map(crate::Completion::parse).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
     /// Customize parser for `--version`
     ///
     /// By default `bpaf` displays version information when program is called with either `--version`
@@ -701,6 +1064,117 @@ impl<T> OptionParser<T> {
         self.info.max_width = width;
         self
     }
+
+    /// Set a color theme used to print help and error messages upon failure
+    ///
+    /// Theme maps each [`Style`](crate::doc::Style) to terminal attributes and is only applied
+    /// when colors are enabled: `bpaf` honors `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE`
+    /// environment variables as well as a global [`ColorChoice`](crate::doc::ColorChoice) such as
+    /// one set by [`color_choice`](crate::batteries::color_choice) battery. Without a custom theme
+    /// `bpaf` uses one picked by `bright-color` or `dull-color` feature.
+    ///
+    /// ```rust
+    /// # use bpaf::{*, doc::*};
+    /// let theme = Theme::monochrome()
+    ///     .style(Style::Literal, "1")
+    ///     .style(Style::Invalid, "1;31");
+    /// let parser = short('v').switch().to_options().theme(theme);
+    /// # drop(parser);
+    /// ```
+    #[must_use]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.info.theme = Some(theme);
+        self
+    }
+
+    /// Set the exit code [`run`](OptionParser::run) uses for a given kind of failure
+    ///
+    /// By default `bpaf` exits with 0 after printing help, version or completion info and with 1
+    /// after printing an error. This method allows to follow a different convention, for example
+    /// `sysexits` codes, or to tell usage errors apart from values that failed to parse or
+    /// validate. [`ParseFailure`] produced by [`run_inner`](OptionParser::run_inner) carries the
+    /// configured code as well, see [`ParseFailure::exit_code`].
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('n')
+    ///     .argument::<u32>("N")
+    ///     .guard(|n| *n > 0, "must be positive")
+    ///     .to_options()
+    ///     .exit_code(FailureKind::Usage, 64)
+    ///     .exit_code(FailureKind::Validation, 65);
+    ///
+    /// let r = parser.run_inner(&["-n", "0"]).unwrap_err();
+    /// assert_eq!(r.kind(), FailureKind::Validation);
+    /// assert_eq!(r.exit_code(), 65);
+    ///
+    /// let r = parser.run_inner(&["-x"]).unwrap_err();
+    /// assert_eq!(r.kind(), FailureKind::Usage);
+    /// assert_eq!(r.exit_code(), 64);
+    /// ```
+    #[must_use]
+    pub fn exit_code(mut self, kind: FailureKind, code: i32) -> Self {
+        self.info.exit_codes.push((kind, code));
+        self
+    }
+}
+
+/// Examples for this parser and all the nested commands
+fn collect_examples(info: &Info, meta: &Meta, res: &mut Vec<&'static str>) {
+    res.extend(info.examples.iter().map(|e| e.0));
+    let mut commands = Vec::new();
+    meta.collect_commands(&mut commands);
+    for cmd in commands {
+        if let Item::Command { meta, info, .. } = cmd {
+            collect_examples(info, meta, res);
+        }
+    }
+}
+
+/// Split a command line into words following POSIX shell quoting rules
+fn split_shell_words(cmdline: &str) -> Result<Vec<String>, &'static str> {
+    let mut words = Vec::new();
+    let mut word = None::<String>;
+    let mut chars = cmdline.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                words.extend(word.take());
+            }
+            '\\' => {
+                let c = chars.next().ok_or("trailing backslash")?;
+                word.get_or_insert_with(String::new).push(c);
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or("unterminated single quote")? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or("unterminated double quote")? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or("unterminated double quote")? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
 }
 
 impl Info {
@@ -714,11 +1188,72 @@ impl Info {
     }
 }
 
+impl Info {
+    /// Render `--help <term>` if searchable help is enabled
+    ///
+    /// Help flag can follow a chain of commands, search happens in the innermost one
+    fn help_search(&self, args: &State, meta: &Meta) -> Option<Doc> {
+        if !self.searchable_help {
+            return None;
+        }
+        #[cfg(feature = "autocomplete")]
+        if args.comp_ref().is_some() {
+            return None;
+        }
+        let mut path = args.path.clone();
+        let mut meta = meta;
+        let mut info = self;
+        let mut commands = Vec::new();
+        let mut key = None;
+        for (ix, arg) in args.items_iter() {
+            if info.help_arg.matches_arg(arg, false) {
+                key = Some(ix);
+                break;
+            }
+            let word = match arg {
+                Arg::Word(w) => w,
+                _ => continue,
+            };
+            commands.clear();
+            meta.collect_commands(&mut commands);
+            for item in &commands {
+                if let Item::Command {
+                    name,
+                    short,
+                    meta: inner_meta,
+                    info: inner_info,
+                    ..
+                } = item
+                {
+                    if *word == **name
+                        || short.map_or(false, |s| word.to_str() == Some(&s.to_string()))
+                    {
+                        path.push(name.clone());
+                        meta = inner_meta;
+                        info = inner_info;
+                        break;
+                    }
+                }
+            }
+        }
+        let key = key?;
+        let term = match args.get(key + 1) {
+            Some(Arg::Word(w) | Arg::ArgWord(w)) => w.to_str()?,
+            _ => return None,
+        };
+        Some(render_help_search(&path, info, meta, &info.meta(), term))
+    }
+}
+
 impl Parser<ExtraParams> for Info {
     fn eval(&self, args: &mut State) -> Result<ExtraParams, Error> {
+        // long name gives detailed help, short one - brief unless used twice
+        let long = args
+            .items_iter()
+            .any(|(_, arg)| matches!(arg, Arg::Long(..)) && self.help_arg.matches_arg(arg, false));
         let help = self.mk_help_parser();
         if help.eval(args).is_ok() {
-            return Ok(ExtraParams::Help(help.eval(args).is_ok()));
+            return Ok(ExtraParams::Help(long || help.eval(args).is_ok()));
         }
 
         if let Some(version) = &self.version {
@@ -732,12 +1267,105 @@ impl Parser<ExtraParams> for Info {
     }
 
     fn meta(&self) -> Meta {
-        let help = self.mk_help_parser().meta();
-        match &self.version {
-            Some(_) => Meta::And(vec![help, self.mk_version_parser().meta()]),
-            None => help,
+        let mut items = vec![self.mk_help_parser().meta()];
+        if self.version.is_some() {
+            items.push(self.mk_version_parser().meta());
+        }
+        if self.help_command {
+            items.push(Meta::from(Item::Command {
+                name: "help".to_owned(),
+                short: None,
+                help: Some(Doc::from(HELP_COMMAND)),
+                group: None,
+                meta: Box::new(Meta::Skip),
+                info: Box::default(),
+            }));
+        }
+        if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Meta::And(items)
+        }
+    }
+}
+
+const HELP_COMMAND: &str = "Prints help for a command";
+
+/// Render help for a command path following the `help` subcommand
+fn help_command(args: &mut State, parser_meta: &Meta, info: &Info) -> ParseFailure {
+    #[cfg(feature = "autocomplete")]
+    if args.touching_last_remove() {
+        args.clear_comps();
+        args.push_command("help", None, &Some(Doc::from(HELP_COMMAND)), None);
+        return ParseFailure::Completion(
+            args.check_complete().unwrap_or_default(),
+            FailureKind::Completion.into(),
+        );
+    }
+
+    let mut path = args.path.clone();
+    let mut meta = parser_meta;
+    let mut info = info;
+    let mut commands = Vec::new();
+    while let Some((ix, arg)) = args.items_iter().next() {
+        let name = match arg {
+            Arg::Word(w) => w.to_str().map(str::to_owned),
+            _ => None,
+        };
+        commands.clear();
+        meta.collect_commands(&mut commands);
+        let found = commands.iter().find_map(|item| match item {
+            Item::Command {
+                name: long,
+                short,
+                meta,
+                info,
+                ..
+            } if name.as_deref().map_or(false, |name| {
+                name == *long || short.map_or(false, |s| name.chars().eq(Some(s)))
+            }) =>
+            {
+                Some((long.as_str(), meta.as_ref(), info.as_ref()))
+            }
+            _ => None,
+        });
+
+        #[cfg(feature = "autocomplete")]
+        if args.comp_ref().is_some() {
+            args.remove(ix);
+            if args.touching_last_remove() {
+                for item in &commands {
+                    if let Item::Command {
+                        name,
+                        short,
+                        help,
+                        group,
+                        ..
+                    } = item
+                    {
+                        args.push_command(name, *short, help, *group);
+                    }
+                }
+                return ParseFailure::Completion(
+                    args.check_complete().unwrap_or_default(),
+                    FailureKind::Completion.into(),
+                );
+            }
+        }
+
+        match found {
+            Some((name, inner_meta, inner_info)) => {
+                args.remove(ix);
+                path.push(name.to_owned());
+                meta = inner_meta;
+                info = inner_info;
+            }
+            None => return Message::Unconsumed(ix).render(args, meta),
         }
     }
+
+    let buffer = render_help(&path, info, meta, &info.meta(), true);
+    ParseFailure::Stdout(buffer, false, FailureKind::Help.into())
 }
 
 #[derive(Clone, Debug)]
@@ -1,4 +1,4 @@
-use crate::{info::Info, meta_help::Metavar, parsers::NamedArg, Doc, Meta};
+use crate::{info::Info, meta_help::Metavar, parsers::NamedArg, Doc, Meta, TypeHint};
 
 #[doc(hidden)]
 #[derive(Clone, Debug)]
@@ -11,11 +11,18 @@ pub enum Item {
     },
     /// Positional item, consumed from the the front of the arguments
     /// <FILE>
-    Positional { metavar: Metavar, help: Option<Doc> },
+    Positional {
+        metavar: Metavar,
+        help: Option<Doc>,
+        /// Default completion derived from the value type
+        hint: Option<TypeHint>,
+    },
     Command {
-        name: &'static str,
+        name: String,
         short: Option<char>,
         help: Option<Doc>,
+        /// Heading commands are listed under in help and completion
+        group: Option<&'static str>,
         meta: Box<Meta>,
         info: Box<Info>,
     },
@@ -26,7 +33,8 @@ pub enum Item {
         name: ShortLong,
         /// used for disambiguation
         shorts: Vec<char>,
-        env: Option<&'static str>,
+        /// environment variables in lookup order
+        env: Vec<&'static str>,
         help: Option<Doc>,
     },
     /// Short or long name followed by a value, consumed anywhere
@@ -37,8 +45,11 @@ pub enum Item {
         /// used for disambiguation
         shorts: Vec<char>,
         metavar: Metavar,
-        env: Option<&'static str>,
+        /// environment variables in lookup order
+        env: Vec<&'static str>,
         help: Option<Doc>,
+        /// Default completion derived from the value type
+        hint: Option<TypeHint>,
     },
 }
 
@@ -153,7 +153,8 @@
 //!    bright-color = ["bpaf/bright-color"]
 //!    dull-color = ["bpaf/dull-color"]
 //!    ```
-//!    Disabled by default.
+//!    Disabled by default. Colors follow `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE`
+//!    conventions, see [`OptionParser::theme`] for custom colors.
 //!
 //!  - `docgen`: generate documentation from help declaration, see [`OptionParser::render_markdown`] and [`doc`](crate::doc). Disabled by default.
 
@@ -170,11 +171,19 @@ mod args;
 pub mod batteries;
 mod buffer;
 #[cfg(feature = "autocomplete")]
+mod complete_cache;
+#[cfg(feature = "autocomplete")]
 mod complete_gen;
 #[cfg(feature = "autocomplete")]
+mod complete_install;
+#[cfg(feature = "autocomplete")]
 mod complete_run;
 #[cfg(feature = "autocomplete")]
 mod complete_shell;
+#[cfg(feature = "autocomplete")]
+mod complete_spec;
+#[cfg(feature = "autocomplete")]
+mod complete_static;
 pub mod doc;
 mod error;
 mod from_os_str;
@@ -187,30 +196,42 @@ pub mod params;
 mod structs;
 #[cfg(test)]
 mod tests;
+mod value_hint;
 
 pub mod parsers {
     //! This module exposes parsers that accept further configuration with builder pattern
     //!
     //! In most cases you won't be using those names directly, they're only listed here to provide
     //! access to documentation
-    #[cfg(feature = "autocomplete")]
-    #[doc(inline)]
-    pub use crate::complete_shell::ParseCompShell;
     #[doc(inline)]
     pub use crate::params::{
-        NamedArg, ParseAny, ParseArgument, ParseCommand, ParseFlag, ParsePositional,
+        ExternalCommand, NamedArg, ParseAny, ParseArgument, ParseCommand, ParseExternal, ParseFlag,
+        ParsePositional,
     };
     #[doc(inline)]
     pub use crate::structs::{
         ParseCollect, ParseCon, ParseCount, ParseFallback, ParseFallbackWith, ParseLast, ParseMany,
         ParseOptional, ParseSome,
     };
+    #[cfg(feature = "autocomplete")]
+    #[doc(inline)]
+    pub use crate::{
+        complete_cache::ParseCompLimit,
+        complete_shell::ParseCompShell,
+        structs::{ParseComp, ParseCompWith},
+    };
 }
 
 // -------------------------------------------------------------------
 
 #[doc(inline)]
-pub use crate::{args::Args, buffer::Doc, error::ParseFailure, info::OptionParser};
+pub use crate::{
+    args::Args,
+    buffer::Doc,
+    error::{ExitStatus, FailureKind, ParseFailure},
+    info::OptionParser,
+    value_hint::{TypeHint, ValueHint},
+};
 
 #[doc(hidden)]
 // used by construct macro, not part of public API
@@ -222,7 +243,7 @@ use crate::{
     buffer::{MetaInfo, Style},
     item::Item,
     params::build_positional,
-    parsers::{NamedArg, ParseAny, ParseCommand, ParsePositional},
+    parsers::{NamedArg, ParseAny, ParseCommand, ParseExternal, ParsePositional},
     structs::{
         ParseCollect, ParseCount, ParseFail, ParseFallback, ParseFallbackWith, ParseGroupHelp,
         ParseGuard, ParseHide, ParseLast, ParseMany, ParseMap, ParseOptional, ParseOrElse,
@@ -230,10 +251,14 @@ use crate::{
     },
 };
 
+#[cfg(feature = "autocomplete")]
+pub use crate::complete_cache::CompletionCache;
+#[cfg(feature = "autocomplete")]
+pub use crate::complete_gen::{Completion, CompletionContext, CompletionKind, CompletionMatcher};
 #[cfg(feature = "autocomplete")]
 pub use crate::complete_shell::ShellComp;
 #[cfg(feature = "autocomplete")]
-use structs::ParseComp;
+use structs::{ParseComp, ParseCompWith};
 
 #[doc(inline)]
 #[cfg(feature = "bpaf_derive")]
@@ -864,6 +889,8 @@ pub trait Parser<T> {
             inner: self,
             value,
             value_str: String::new(),
+            #[cfg(feature = "autocomplete")]
+            current: None,
         }
     }
     // }}}
@@ -892,6 +919,8 @@ pub trait Parser<T> {
             inner_res: PhantomData,
             fallback,
             value_str: String::new(),
+            #[cfg(feature = "autocomplete")]
+            current: None,
             err: PhantomData,
         }
     }
@@ -1106,6 +1135,47 @@ pub trait Parser<T> {
             group: None,
         }
     }
+
+    /// Dynamic shell completion that depends on other arguments
+    ///
+    /// Works like [`complete`](Parser::complete), but the function also gets a
+    /// [`CompletionContext`] with arguments typed on the command line so far. This way
+    /// suggestions for `--branch` can depend on a value already given to `--repo`, regardless
+    /// of the order parsers are composed in.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// fn branches(input: &String, ctx: &CompletionContext) -> Vec<(String, Option<String>)> {
+    ///     let names: &[&str] = match ctx.value("--repo") {
+    ///         Some("bpaf") => &["master", "v0.9"],
+    ///         _ => &["main"],
+    ///     };
+    ///     names
+    ///         .iter()
+    ///         .filter(|name| name.starts_with(input.as_str()))
+    ///         .map(|name| (name.to_string(), None))
+    ///         .collect()
+    /// }
+    ///
+    /// fn branch() -> impl Parser<String> {
+    ///     long("branch")
+    ///         .argument("BRANCH")
+    ///         .complete_with_context(branches)
+    /// }
+    /// ```
+    #[cfg(feature = "autocomplete")]
+    fn complete_with_context<M, F>(self, op: F) -> ParseCompWith<Self, F>
+    where
+        M: Into<String>,
+        F: Fn(&T, &CompletionContext) -> Vec<(M, Option<M>)>,
+        Self: Sized + Parser<T>,
+    {
+        ParseCompWith {
+            inner: self,
+            op,
+            group: None,
+        }
+    }
     // }}}
 
     // {{{
@@ -1305,6 +1375,9 @@ pub fn short(short: char) -> NamedArg {
         env: Vec::new(),
         long: Vec::new(),
         help: None,
+        long_help: None,
+        #[cfg(feature = "autocomplete")]
+        complete_env: false,
     }
 }
 
@@ -1322,6 +1395,9 @@ pub fn long(long: &'static str) -> NamedArg {
         long: vec![long],
         env: Vec::new(),
         help: None,
+        long_help: None,
+        #[cfg(feature = "autocomplete")]
+        complete_env: false,
     }
 }
 
@@ -1350,7 +1426,10 @@ pub fn env(variable: &'static str) -> NamedArg {
         short: Vec::new(),
         long: Vec::new(),
         help: None,
+        long_help: None,
         env: vec![variable],
+        #[cfg(feature = "autocomplete")]
+        complete_env: false,
     }
 }
 
@@ -1402,6 +1481,53 @@ pub fn positional<T>(metavar: &'static str) -> ParsePositional<T> {
     build_positional(metavar)
 }
 
+/// Dispatch unknown commands to external executables, the way `git` and `cargo` do
+///
+/// Parser looks for executables named `{prefix}{name}` in directories listed in `PATH`, or
+/// in directories given to [`search_in`](ParseExternal::search_in), and succeeds when the
+/// first positional item matches one of the discovered names. Everything to the right of the
+/// command name is captured unchanged into [`ExternalCommand`](parsers::ExternalCommand).
+///
+/// Discovered commands are listed in `--help` under "Available commands", participate in shell
+/// completion and "did you mean" suggestions. Regular [commands](OptionParser::command) take
+/// priority over external ones with the same name.
+///
+/// # Combinatoric usage
+/// ```rust
+/// # use bpaf::*;
+/// # use bpaf::parsers::ExternalCommand;
+/// #[derive(Debug, Clone)]
+/// enum Cmd {
+///     Build { release: bool },
+///     External(ExternalCommand),
+/// }
+///
+/// fn options() -> OptionParser<Cmd> {
+///     let release = long("release").switch();
+///     let build = construct!(Cmd::Build { release })
+///         .to_options()
+///         .command("build");
+///     let external = external_commands("mytool-").map(Cmd::External);
+///     construct!([build, external]).to_options()
+/// }
+/// ```
+///
+/// # Example
+/// ```console
+/// $ mytool foo --bar baz
+/// // runs `mytool-foo --bar baz` if `mytool-foo` is present in `PATH`
+/// ```
+#[must_use]
+pub fn external_commands(prefix: &'static str) -> ParseExternal {
+    ParseExternal {
+        prefix,
+        dirs: None,
+        help: None,
+        group: None,
+        found: std::cell::RefCell::new(None),
+    }
+}
+
 #[doc(hidden)]
 #[deprecated = "You should switch from command(name, sub) to sub.command(name)"]
 pub fn command<T>(name: &'static str, subparser: OptionParser<T>) -> ParseCommand<T>
@@ -1414,6 +1540,8 @@ where
         help: subparser.short_descr().map(Into::into),
         subparser,
         adjacent: false,
+        group: None,
+        long_help: None,
     }
 }
 
@@ -29,6 +29,9 @@ pub enum Meta {
     CustomUsage(Box<Meta>, Box<Doc>),
     /// this meta must be prefixed with -- in unsage group
     Strict(Box<Meta>),
+    /// Items inside use custom shell completion, `None` stands for a completion function
+    #[cfg(feature = "autocomplete")]
+    Comp(Box<Meta>, Option<crate::ShellComp>),
 }
 
 // to get std::mem::take to work
@@ -126,6 +129,8 @@ impl Meta {
                 | Meta::Subsection(m, _)
                 | Meta::Strict(m)
                 | Meta::Suffix(m, _) => go(m, is_pos, v),
+                #[cfg(feature = "autocomplete")]
+                Meta::Comp(m, _) => go(m, is_pos, v),
                 Meta::Skip => {}
             }
         }
@@ -167,6 +172,8 @@ impl Meta {
             | Meta::Subsection(x, _)
             | Meta::Suffix(x, _)
             | Meta::CustomUsage(x, _) => Self::first_item(x),
+            #[cfg(feature = "autocomplete")]
+            Meta::Comp(x, _) => Self::first_item(x),
         }
     }
 
@@ -270,6 +277,11 @@ impl Meta {
                 m.normalize(for_usage, norm);
                 *self = std::mem::take(m);
             }
+            #[cfg(feature = "autocomplete")]
+            Meta::Comp(m, _) => {
+                m.normalize(for_usage, norm);
+                *self = std::mem::take(m);
+            }
             Meta::Item(i) => i.normalize(for_usage),
             Meta::Skip => {
                 // nothing to do with items and skip just bubbles upwards
@@ -345,7 +357,38 @@ impl Meta {
             | Meta::Many(m) => {
                 m.collect_shorts(flags, args);
             }
+            #[cfg(feature = "autocomplete")]
+            Meta::Comp(m, _) => m.collect_shorts(flags, args),
             Meta::Skip | Meta::Strict(_) => {}
         }
     }
+
+    /// collect commands available at this level, nested commands are not included
+    pub(crate) fn collect_commands<'a>(&'a self, res: &mut Vec<&'a Item>) {
+        match self {
+            Meta::And(xs) | Meta::Or(xs) => {
+                for x in xs {
+                    x.collect_commands(res);
+                }
+            }
+            Meta::Item(m) => {
+                if let Item::Command { .. } = &**m {
+                    res.push(m);
+                }
+            }
+            Meta::CustomUsage(m, _)
+            | Meta::Required(m)
+            | Meta::Optional(m)
+            | Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Suffix(m, _)
+            | Meta::Strict(m)
+            | Meta::Many(m) => {
+                m.collect_commands(res);
+            }
+            #[cfg(feature = "autocomplete")]
+            Meta::Comp(m, _) => m.collect_commands(res),
+            Meta::Skip => {}
+        }
+    }
 }
@@ -34,22 +34,23 @@ pub(crate) enum HelpItem<'a> {
         help: Option<&'a Doc>,
     },
     Command {
-        name: &'static str,
+        name: &'a str,
         short: Option<char>,
         help: Option<&'a Doc>,
+        group: Option<&'static str>,
         meta: &'a Meta,
         #[cfg(feature = "docgen")]
         info: &'a Info,
     },
     Flag {
         name: ShortLong,
-        env: Option<&'static str>,
+        env: &'a [&'static str],
         help: Option<&'a Doc>,
     },
     Argument {
         name: ShortLong,
         metavar: Metavar,
-        env: Option<&'static str>,
+        env: &'a [&'static str],
         help: Option<&'a Doc>,
     },
     AnywhereStart {
@@ -75,6 +76,29 @@ impl HelpItem<'_> {
         }
     }
 
+    /// Check if rendered item contains a lowercase search term
+    fn matches(&self, term: &str) -> bool {
+        let mut buf = Doc::default();
+        write_help_item(&mut buf, self, false);
+        buf.monochrome(true).to_lowercase().contains(term)
+    }
+
+    /// Same as [`matches`](HelpItem::matches), but decorations follow the item they decorate
+    fn matches_after(&self, term: &str, prev: bool) -> bool {
+        match self {
+            HelpItem::DecorSuffix { .. } => prev,
+            _ => self.matches(term),
+        }
+    }
+
+    /// Command group heading, only commands can have it
+    fn group(&self) -> Option<&'static str> {
+        match self {
+            HelpItem::Command { group, .. } => *group,
+            _ => None,
+        }
+    }
+
     fn ty(&self) -> HiTy {
         match self {
             HelpItem::GroupStart { ty, .. }
@@ -188,6 +212,8 @@ impl Meta {
             | Meta::Suffix(x, _)
             | Meta::Strict(x)
             | Meta::CustomUsage(x, _) => x.peek_front_ty(),
+            #[cfg(feature = "autocomplete")]
+            Meta::Comp(x, _) => x.peek_front_ty(),
             Meta::Item(i) => Some(HiTy::from(i.as_ref())),
             Meta::Skip => None,
         }
@@ -219,6 +245,8 @@ impl<'a> HelpItems<'a> {
                 | Meta::Optional(x)
                 | Meta::Many(x)
                 | Meta::Strict(x) => go(hi, x, no_ss),
+                #[cfg(feature = "autocomplete")]
+                Meta::Comp(x, _) => go(hi, x, no_ss),
                 Meta::Item(item) => {
                     if matches!(item.as_ref(), Item::Positional { help: None, .. }) {
                         return;
@@ -248,6 +276,50 @@ impl<'a> HelpItems<'a> {
         go(self, meta, false);
     }
 
+    /// Keep only items matching a lowercase search term
+    ///
+    /// Group headers are kept if anything inside the group matches, all the group is kept if
+    /// the header itself matches. Adjacent blocks are kept or dropped as a whole.
+    fn retain_matching(&mut self, term: &str) {
+        let items = &self.items;
+        let mut keep = vec![false; items.len()];
+        let mut ix = 0;
+        while ix < items.len() {
+            let end = match items[ix] {
+                HelpItem::GroupStart { .. } => {
+                    (ix..items.len()).find(|&e| matches!(items[e], HelpItem::GroupEnd { .. }))
+                }
+                HelpItem::AnywhereStart { .. } => {
+                    (ix..items.len()).find(|&e| matches!(items[e], HelpItem::AnywhereStop { .. }))
+                }
+                _ => None,
+            };
+            match end {
+                Some(end) => {
+                    let whole =
+                        matches!(items[ix], HelpItem::GroupStart { .. }) && items[ix].matches(term);
+                    for i in ix + 1..end {
+                        keep[i] = whole || items[i].matches_after(term, keep[i - 1]);
+                    }
+                    let any = keep[ix + 1..end].iter().any(|k| *k);
+                    let anywhere = matches!(items[ix], HelpItem::AnywhereStart { .. });
+                    for k in &mut keep[ix..=end] {
+                        *k = *k || any && anywhere;
+                    }
+                    keep[ix] = any;
+                    keep[end] = any;
+                    ix = end + 1;
+                }
+                None => {
+                    keep[ix] = items[ix].matches_after(term, ix > 0 && keep[ix - 1]);
+                    ix += 1;
+                }
+            }
+        }
+        let mut keep = keep.into_iter();
+        self.items.retain(|_| keep.next().unwrap_or(false));
+    }
+
     fn find_group(&self) -> Option<std::ops::RangeInclusive<usize>> {
         let start = self
             .items
@@ -280,7 +352,11 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
     // {{{
     fn from(item: &'a Item) -> Self {
         match item {
-            Item::Positional { metavar, help } => Self::Positional {
+            Item::Positional {
+                metavar,
+                help,
+                hint: _,
+            } => Self::Positional {
                 metavar: *metavar,
                 help: help.as_ref(),
             },
@@ -288,6 +364,7 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 name,
                 short,
                 help,
+                group,
                 meta,
                 #[cfg(feature = "docgen")]
                 info,
@@ -297,6 +374,7 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 name,
                 short: *short,
                 help: help.as_ref(),
+                group: *group,
                 meta,
                 #[cfg(feature = "docgen")]
                 info,
@@ -308,7 +386,7 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 shorts: _,
             } => Self::Flag {
                 name: *name,
-                env: *env,
+                env,
                 help: help.as_ref(),
             },
             Item::Argument {
@@ -317,10 +395,11 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 env,
                 help,
                 shorts: _,
+                hint: _,
             } => Self::Argument {
                 name: *name,
                 metavar: *metavar,
-                env: *env,
+                env,
                 help: help.as_ref(),
             },
             Item::Any {
@@ -402,6 +481,7 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
             name,
             short,
             help,
+            group: _,
             meta: _,
             #[cfg(feature = "docgen")]
                 info: _,
@@ -428,7 +508,7 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
                 buf.doc(help);
                 buf.token(Token::BlockEnd(Block::ItemBody));
             }
-            if let Some(env) = env {
+            if let Some(env) = env.first() {
                 let val = if std::env::var_os(env).is_some() {
                     ": set"
                 } else {
@@ -466,7 +546,7 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
                 buf.token(Token::BlockEnd(Block::ItemBody));
             }
 
-            if let Some(env) = env {
+            if let Some(env) = env.first() {
                 let val = match std::env::var_os(env) {
                     Some(s) => std::borrow::Cow::from(format!(" = {:?}", s.to_string_lossy())),
                     None => std::borrow::Cow::Borrowed(": N/A"),
@@ -537,6 +617,30 @@ pub(crate) fn render_help(
         buf.token(Token::BlockEnd(Block::Block));
     }
 
+    write_usage(&mut buf, path, info, parser_meta);
+
+    if let Some(t) = &info.header {
+        buf.token(Token::BlockStart(Block::Block));
+        buf.doc(t);
+        buf.token(Token::BlockEnd(Block::Block));
+    }
+
+    let mut items = HelpItems::default();
+    items.append_meta(parser_meta);
+    items.append_meta(help_meta);
+
+    buf.write_help_item_groups(items, include_env);
+    buf.write_examples(info);
+
+    if let Some(footer) = &info.footer {
+        buf.token(Token::BlockStart(Block::Block));
+        buf.doc(footer);
+        buf.token(Token::BlockEnd(Block::Block));
+    }
+    buf
+}
+
+fn write_usage(buf: &mut Doc, path: &[String], info: &Info, parser_meta: &Meta) {
     buf.token(Token::BlockStart(Block::Block));
     if let Some(usage) = &info.usage {
         buf.doc(usage);
@@ -549,27 +653,99 @@ pub(crate) fn render_help(
         buf.token(Token::BlockEnd(Block::Mono));
     }
     buf.token(Token::BlockEnd(Block::Block));
+}
 
-    if let Some(t) = &info.header {
-        buf.token(Token::BlockStart(Block::Block));
-        buf.doc(t);
-        buf.token(Token::BlockEnd(Block::Block));
-    }
+/// Render usage line and only those help items that match a search term
+///
+/// Matches from nested commands are listed after the main items, one section per command
+#[inline(never)]
+pub(crate) fn render_help_search(
+    path: &[String],
+    info: &Info,
+    parser_meta: &Meta,
+    help_meta: &Meta,
+    term: &str,
+) -> Doc {
+    let term = term.to_lowercase();
+    let mut buf = Doc::default();
+    write_usage(&mut buf, path, info, parser_meta);
 
     let mut items = HelpItems::default();
     items.append_meta(parser_meta);
     items.append_meta(help_meta);
+    items.retain_matching(&term);
+    let mut found = !items.items.is_empty();
+    buf.write_help_item_groups(items, true);
 
-    buf.write_help_item_groups(items, include_env);
+    found |= write_nested_matches(&mut buf, parser_meta, &mut Vec::new(), &term);
 
-    if let Some(footer) = &info.footer {
+    if !found {
         buf.token(Token::BlockStart(Block::Block));
-        buf.doc(footer);
+        buf.text("Nothing matches ");
+        buf.token(Token::BlockStart(Block::TermRef));
+        buf.literal(&term);
+        buf.token(Token::BlockEnd(Block::TermRef));
         buf.token(Token::BlockEnd(Block::Block));
     }
     buf
 }
 
+/// Write items matching the search term for every nested command, returns true if any matched
+fn write_nested_matches<'a>(
+    buf: &mut Doc,
+    meta: &'a Meta,
+    path: &mut Vec<&'a str>,
+    term: &str,
+) -> bool {
+    let mut commands = Vec::new();
+    meta.collect_commands(&mut commands);
+    let mut found = false;
+    for item in commands {
+        if let Item::Command { name, meta, .. } = item {
+            path.push(name);
+            let mut items = HelpItems::default();
+            items.append_meta(meta);
+            items.retain_matching(term);
+            let mut xs = items
+                .items
+                .iter()
+                .filter(|item| {
+                    !matches!(
+                        item,
+                        HelpItem::GroupStart { .. }
+                            | HelpItem::GroupEnd { .. }
+                            | HelpItem::AnywhereStart { .. }
+                            | HelpItem::AnywhereStop { .. }
+                            | HelpItem::DecorSuffix { .. }
+                    )
+                })
+                .peekable();
+            if xs.peek().is_some() {
+                found = true;
+                buf.token(Token::BlockStart(Block::Block));
+                buf.token(Token::BlockStart(Block::Section2));
+                buf.write_str(
+                    &format!("Found in `{}` command:", path.join(" ")),
+                    Style::Emphasis,
+                );
+                buf.token(Token::BlockEnd(Block::Section2));
+                buf.token(Token::BlockStart(Block::DefinitionList));
+                let mut dd = Dedup::default();
+                for item in xs {
+                    if dd.check(item) {
+                        write_help_item(buf, item, true);
+                    }
+                }
+                buf.token(Token::BlockEnd(Block::DefinitionList));
+                buf.token(Token::BlockEnd(Block::Block));
+            }
+            found |= write_nested_matches(buf, meta, path, term);
+            path.pop();
+        }
+    }
+    found
+}
+
 #[derive(Default)]
 struct Dedup {
     items: BTreeSet<String>,
@@ -635,13 +811,36 @@ impl Doc {
             (HiTy::Flag, "Available options:"),
             (HiTy::Command, "Available commands:"),
         ] {
-            self.write_help_items(&items, ty, name, include_env);
+            self.write_help_items(&items, ty, None, name, include_env);
+        }
+
+        let mut groups = Vec::new();
+        for item in items.items_of_ty(HiTy::Command) {
+            if let Some(group) = item.group() {
+                if !groups.contains(&group) {
+                    groups.push(group);
+                }
+            }
+        }
+        for group in groups {
+            let name = format!("{}:", group);
+            self.write_help_items(&items, HiTy::Command, Some(group), &name, include_env);
         }
     }
 
     #[inline(never)]
-    fn write_help_items(&mut self, items: &HelpItems, ty: HiTy, name: &str, include_env: bool) {
-        let mut xs = items.items_of_ty(ty).peekable();
+    fn write_help_items(
+        &mut self,
+        items: &HelpItems,
+        ty: HiTy,
+        group: Option<&str>,
+        name: &str,
+        include_env: bool,
+    ) {
+        let mut xs = items
+            .items_of_ty(ty)
+            .filter(|item| item.group() == group)
+            .peekable();
         if xs.peek().is_some() {
             self.token(Token::BlockStart(Block::Block));
             self.token(Token::BlockStart(Block::Section2));
@@ -659,6 +858,28 @@ impl Doc {
         }
     }
 
+    /// Examples section, see [`OptionParser::example`](crate::OptionParser::example)
+    pub(crate) fn write_examples(&mut self, info: &Info) {
+        if info.examples.is_empty() {
+            return;
+        }
+        self.token(Token::BlockStart(Block::Block));
+        self.token(Token::BlockStart(Block::Section2));
+        self.write_str("Examples:", Style::Emphasis);
+        self.token(Token::BlockEnd(Block::Section2));
+        self.token(Token::BlockStart(Block::DefinitionList));
+        for (cmdline, descr) in &info.examples {
+            self.token(Token::BlockStart(Block::ItemTerm));
+            self.write_str(cmdline, Style::Literal);
+            self.token(Token::BlockEnd(Block::ItemTerm));
+            self.token(Token::BlockStart(Block::ItemBody));
+            self.doc(descr);
+            self.token(Token::BlockEnd(Block::ItemBody));
+        }
+        self.token(Token::BlockEnd(Block::DefinitionList));
+        self.token(Token::BlockEnd(Block::Block));
+    }
+
     pub(crate) fn write_path(&mut self, path: &[String]) {
         for item in path {
             self.write_str(item, Style::Literal);
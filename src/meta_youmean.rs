@@ -4,9 +4,9 @@ use crate::{
     Meta, State,
 };
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub(crate) enum Variant {
-    CommandLong(&'static str),
+    CommandLong(String),
     Flag(ShortLong),
 }
 
@@ -62,7 +62,7 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
                 // - typo in a short or a long name
                 // - there is a nested command that matches perfectly - try using that
                 let distance = damerau_levenshtein(&actual, name);
-                improve(distance, Variant::CommandLong(name));
+                improve(distance, Variant::CommandLong((*name).to_owned()));
 
                 // scan nested items and look for exact matches only
                 nested.items.clear();
@@ -71,7 +71,7 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
                     match item {
                         HelpItem::Command { name: nname, .. } => {
                             if *nname == actual {
-                                nest = Some((name, Variant::CommandLong(nname)));
+                                nest = Some((name, Variant::CommandLong((*nname).to_owned())));
                             }
                         }
                         HelpItem::Flag { name: nname, .. }
@@ -140,7 +140,7 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
 /// returns `usize::MAX` if there's no common characters at all mostly to avoid
 /// confusing error messages - "you typed 'foo', maybe you ment 'bar'" where
 /// 'foo' and 'bar' don't have anything in common
-fn damerau_levenshtein(a: &str, b: &str) -> usize {
+pub(crate) fn damerau_levenshtein(a: &str, b: &str) -> usize {
     #![allow(clippy::many_single_char_names)]
     let a_len = a.chars().count();
     let b_len = b.chars().count();
@@ -59,7 +59,7 @@
 //!
 #![cfg_attr(not(doctest), doc = include_str!("docs2/command.md"))]
 //!
-use std::{ffi::OsString, marker::PhantomData, str::FromStr};
+use std::{cell::RefCell, ffi::OsString, marker::PhantomData, path::PathBuf, str::FromStr};
 
 use crate::{
     args::{Arg, State},
@@ -67,7 +67,7 @@ use crate::{
     from_os_str::parse_os_str,
     item::ShortLong,
     meta_help::Metavar,
-    Doc, Error, Item, Meta, OptionParser, Parser,
+    Doc, Error, Item, Meta, OptionParser, Parser, TypeHint, ValueHint,
 };
 
 #[cfg(doc)]
@@ -109,14 +109,18 @@ pub struct NamedArg {
     pub(crate) long: Vec<&'static str>,
     pub(crate) env: Vec<&'static str>,
     pub(crate) help: Option<Doc>,
+    pub(crate) long_help: Option<Doc>,
+    /// Offer value from the environment variable in completion
+    #[cfg(feature = "autocomplete")]
+    pub(crate) complete_env: bool,
 }
 
 impl NamedArg {
     pub(crate) fn flag_item(&self) -> Option<Item> {
         Some(Item::Flag {
             name: ShortLong::try_from(self).ok()?,
-            help: self.help.clone(),
-            env: self.env.first().copied(),
+            help: Doc::with_details(&self.help, &self.long_help),
+            env: self.env.clone(),
             shorts: self.short.clone(),
         })
     }
@@ -161,11 +165,41 @@ impl NamedArg {
         self
     }
 
+    /// Offer value from the [`env`](NamedArg::env()) variable in shell completion
+    ///
+    /// When completing a value for this argument shell completion lists the value from the
+    /// environment variable first, described as `current: from $VAR`. Environment variables can
+    /// contain secrets such as tokens so this is off by default.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("region")
+    ///     .env("BPAF_DOC_REGION")
+    ///     .complete_env_value()
+    ///     .argument::<String>("REGION")
+    ///     .to_options();
+    ///
+    /// std::env::set_var("BPAF_DOC_REGION", "eu-west-1");
+    /// let r = parser
+    ///     .run_inner(Args::from(&["--region", "eu"]).set_comp(0))
+    ///     .unwrap_err()
+    ///     .unwrap_stdout();
+    /// assert_eq!(r, "eu-west-1\teu-west-1\t\tcurrent: from $BPAF_DOC_REGION\n\tREGION\t\t\n\n");
+    /// ```
+    #[cfg(feature = "autocomplete")]
+    #[must_use]
+    pub fn complete_env_value(mut self) -> Self {
+        self.complete_env = true;
+        self
+    }
+
     /// Add a help message to a `flag`/`switch`/`argument`
     ///
     /// `bpaf` converts doc comments and string into help by following those rules:
-    /// 1. Everything up to the first blank line is included into a "short" help message
-    /// 2. Everything is included into a "long" help message
+    /// 1. Everything up to the first blank line is included into a "short" help message,
+    ///    `-h` shows it
+    /// 2. Everything is included into a "long" help message, `--help` and documentation
+    ///    generators show it
     /// 3. `bpaf` preserves linebreaks followed by a line that starts with a space
     /// 4. Linebreaks are removed otherwise
     ///
@@ -182,6 +216,36 @@ impl NamedArg {
         self
     }
 
+    /// Add a detailed help message to a `flag`/`switch`/`argument`
+    ///
+    /// Brief help set with [`help`](NamedArg::help) is shown by `-h`, detailed one is shown
+    /// after the brief one by `--help` and in generated documentation. Without the brief help
+    /// `bpaf` uses the first paragraph of the detailed one.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = short('v')
+    ///     .long("verbose")
+    ///     .help("Produce more output")
+    ///     .long_help("Can be used multiple times, each time increases the verbosity")
+    ///     .switch()
+    ///     .to_options();
+    ///
+    /// let brief = parser.run_inner(&["-h"]).unwrap_err().unwrap_stdout();
+    /// assert!(!brief.contains("multiple times"));
+    ///
+    /// let detailed = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    /// assert!(detailed.contains("multiple times"));
+    /// ```
+    #[must_use]
+    pub fn long_help<M>(mut self, help: M) -> Self
+    where
+        M: Into<Doc>,
+    {
+        self.long_help = Some(help.into());
+        self
+    }
+
     /// Simple boolean flag
     ///
     /// A special case of a [`flag`](NamedArg::flag) that gets decoded into a `bool`, mostly serves as a convenient
@@ -309,6 +373,8 @@ impl<T> OptionParser<T> {
             help: self.short_descr().map(Into::into),
             subparser: self,
             adjacent: false,
+            group: None,
+            long_help: None,
         }
     }
 }
@@ -323,6 +389,8 @@ pub struct ParseCommand<T> {
     pub(crate) help: Option<Doc>,
     pub(crate) subparser: OptionParser<T>,
     pub(crate) adjacent: bool,
+    pub(crate) group: Option<&'static str>,
+    pub(crate) long_help: Option<Doc>,
 }
 
 impl<P> ParseCommand<P> {
@@ -381,6 +449,19 @@ impl<P> ParseCommand<P> {
         self
     }
 
+    /// Add a detailed description to a command
+    ///
+    /// `--help` shows it in the list of commands after the brief one, `-h` shows only the
+    /// brief description. See [`NamedArg::long_help`].
+    #[must_use]
+    pub fn long_help<M>(mut self, help: M) -> Self
+    where
+        M: Into<Doc>,
+    {
+        self.long_help = Some(help.into());
+        self
+    }
+
     /// Add a custom short alias for a command
     ///
     /// Behavior is similar to [`short`](NamedArg::short), only first short name is visible.
@@ -422,6 +503,62 @@ impl<P> ParseCommand<P> {
         self.adjacent = true;
         self
     }
+
+    /// List this command under a separate heading
+    ///
+    /// By default all the commands are listed under "Available commands:" in help, markdown and
+    /// manpage output. Commands with a group are listed after that, each group under its own
+    /// heading, in order of the first appearance. Shells that support grouping completions show
+    /// them grouped as well.
+    ///
+    /// # Combinatoric usage
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// fn commands() -> impl Parser<&'static str> {
+    ///     let commit = pure("commit")
+    ///         .to_options()
+    ///         .descr("Record changes to the repository")
+    ///         .command("commit")
+    ///         .group("Porcelain commands");
+    ///     let cat_file = pure("cat-file")
+    ///         .to_options()
+    ///         .descr("Provide content of repository objects")
+    ///         .command("cat-file")
+    ///         .group("Plumbing commands");
+    ///     construct!([commit, cat_file])
+    /// }
+    /// ```
+    ///
+    /// # Derive usage
+    /// ```rust
+    /// # use bpaf::*;
+    /// #[derive(Debug, Clone, Bpaf)]
+    /// enum Cmd {
+    ///     /// Record changes to the repository
+    ///     #[bpaf(command, group("Porcelain commands"))]
+    ///     Commit,
+    ///     /// Provide content of repository objects
+    ///     #[bpaf(command("cat-file"), group("Plumbing commands"))]
+    ///     CatFile,
+    /// }
+    /// ```
+    ///
+    /// # Example
+    /// ```console
+    /// $ app --help
+    ///     <skip>
+    /// Porcelain commands:
+    ///     commit    Record changes to the repository
+    ///
+    /// Plumbing commands:
+    ///     cat-file  Provide content of repository objects
+    /// ```
+    #[must_use]
+    pub fn group(mut self, group: &'static str) -> Self {
+        self.group = Some(group);
+        self
+    }
 }
 
 impl<T> Parser<T> for ParseCommand<T> {
@@ -440,7 +577,12 @@ impl<T> Parser<T> for ParseCommand<T> {
                 // in completion mode prefer to autocomplete the command name vs going inside the
                 // parser
                 args.clear_comps();
-                args.push_command(self.longs[0], self.shorts.first().copied(), &self.help);
+                args.push_command(
+                    self.longs[0],
+                    self.shorts.first().copied(),
+                    &self.help,
+                    self.group,
+                );
                 return Err(Error(Message::Missing(Vec::new())));
             }
 
@@ -484,7 +626,12 @@ impl<T> Parser<T> for ParseCommand<T> {
             }
         } else {
             #[cfg(feature = "autocomplete")]
-            args.push_command(self.longs[0], self.shorts.first().copied(), &self.help);
+            args.push_command(
+                self.longs[0],
+                self.shorts.first().copied(),
+                &self.help,
+                self.group,
+            );
 
             let missing = MissingItem {
                 item: self.item(),
@@ -503,15 +650,269 @@ impl<T> Parser<T> for ParseCommand<T> {
 impl<T> ParseCommand<T> {
     fn item(&self) -> Item {
         Item::Command {
-            name: self.longs[0],
+            name: self.longs[0].to_owned(),
             short: self.shorts.first().copied(),
-            help: self.help.clone(),
+            help: Doc::with_details(&self.help, &self.long_help),
+            group: self.group,
             meta: Box::new(self.subparser.inner.meta()),
             info: Box::new(self.subparser.info.clone()),
         }
     }
 }
 
+/// Command that is not known to the parser and should be dispatched to an external executable
+///
+/// Produced by [`external_commands`](crate::external_commands), contains the name of the
+/// command as typed by the user, path to the executable and all the remaining arguments
+/// unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalCommand {
+    /// Command name as typed by the user: `foo` in `mytool foo --bar`
+    pub name: String,
+    /// Full path to the discovered executable: `/usr/bin/mytool-foo`
+    pub path: PathBuf,
+    /// All the items after the command name, passed unchanged
+    pub args: Vec<OsString>,
+}
+
+impl ExternalCommand {
+    /// Create a [`Command`](std::process::Command) to run the external executable
+    ///
+    /// ```no_run
+    /// # use bpaf::*;
+    /// let ext = external_commands("mytool-").to_options().run();
+    /// let status = ext.command().status();
+    /// ```
+    #[must_use]
+    pub fn command(&self) -> std::process::Command {
+        let mut cmd = std::process::Command::new(&self.path);
+        cmd.args(&self.args);
+        cmd
+    }
+}
+
+/// Builder structure for [`external_commands`](crate::external_commands)
+///
+/// Looks up executables lazily, the first time parser needs them to parse the input, render
+/// help or complete a command name.
+pub struct ParseExternal {
+    pub(crate) prefix: &'static str,
+    /// Directories to search in, `None` stands for `PATH`
+    pub(crate) dirs: Option<Vec<PathBuf>>,
+    pub(crate) help: Option<Doc>,
+    pub(crate) group: Option<&'static str>,
+    pub(crate) found: RefCell<Option<Vec<(String, PathBuf)>>>,
+}
+
+impl ParseExternal {
+    /// Add a help message to every discovered external command
+    #[must_use]
+    pub fn help<M>(mut self, help: M) -> Self
+    where
+        M: Into<Doc>,
+    {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// List every discovered external command under a separate heading
+    ///
+    /// See [`ParseCommand::group`] for details.
+    #[must_use]
+    pub fn group(mut self, group: &'static str) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Search for executables in those directories instead of `PATH`
+    #[must_use]
+    pub fn search_in<I, P>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.dirs = Some(dirs.into_iter().map(Into::into).collect());
+        self.found = RefCell::new(None);
+        self
+    }
+
+    /// Discovered commands, sorted by name
+    fn commands(&self) -> std::cell::Ref<'_, Vec<(String, PathBuf)>> {
+        if self.found.borrow().is_none() {
+            let found = match &self.dirs {
+                Some(dirs) => discover_external(self.prefix, dirs.iter().cloned()),
+                None => match std::env::var_os("PATH") {
+                    Some(path) => discover_external(self.prefix, std::env::split_paths(&path)),
+                    None => Vec::new(),
+                },
+            };
+            *self.found.borrow_mut() = Some(found);
+        }
+        std::cell::Ref::map(self.found.borrow(), |f| f.as_ref().unwrap())
+    }
+
+    fn item(&self, name: Option<&str>) -> Item {
+        match name {
+            Some(name) => Item::Command {
+                name: name.to_owned(),
+                short: None,
+                help: self.help.clone(),
+                group: self.group,
+                meta: Box::new(Meta::Skip),
+                info: Box::default(),
+            },
+            None => Item::Positional {
+                metavar: Metavar("COMMAND"),
+                help: None,
+                hint: None,
+            },
+        }
+    }
+}
+
+/// Look for executables named `{prefix}{name}`, earlier directories take priority
+fn discover_external<I>(prefix: &str, dirs: I) -> Vec<(String, PathBuf)>
+where
+    I: Iterator<Item = PathBuf>,
+{
+    let mut res: Vec<(String, PathBuf)> = Vec::new();
+    for dir in dirs {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let name = match file_name
+                .to_str()
+                .and_then(|n| n.strip_prefix(prefix))
+                .map(|n| n.strip_suffix(std::env::consts::EXE_SUFFIX).unwrap_or(n))
+            {
+                Some(name) if !name.is_empty() => name,
+                _ => continue,
+            };
+            let path = entry.path();
+            if !is_executable(&path) || res.iter().any(|(n, _)| *n == name) {
+                continue;
+            }
+            res.push((name.to_owned(), path));
+        }
+    }
+    res.sort_by(|a, b| a.0.cmp(&b.0));
+    res
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).map_or(false, |m| {
+        m.is_file() && m.permissions().mode() & 0o111 != 0
+    })
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &std::path::Path) -> bool {
+    std::fs::metadata(path).map_or(false, |m| m.is_file())
+}
+
+impl Parser<ExternalCommand> for ParseExternal {
+    fn eval(&self, args: &mut State) -> Result<ExternalCommand, Error> {
+        let commands = self.commands();
+        let (ix, name) = match args.items_iter().next() {
+            Some((ix, Arg::Word(w))) => (ix, w.to_str()),
+            _ => (0, None),
+        };
+        let found = name.and_then(|name| commands.iter().find(|(n, _)| n == name));
+
+        let (name, path) = match found {
+            Some((name, path)) => (name.clone(), path.clone()),
+            None => {
+                #[cfg(feature = "autocomplete")]
+                for (name, _) in commands.iter() {
+                    args.push_command(name, None, &self.help, self.group);
+                }
+                let mut names = commands.iter().map(|c| Some(c.0.as_str())).peekable();
+                let missing = if names.peek().is_some() {
+                    names.map(|name| self.item(name)).collect()
+                } else {
+                    vec![self.item(None)]
+                };
+                let missing = missing
+                    .into_iter()
+                    .map(|item| MissingItem {
+                        item,
+                        position: args.scope().start,
+                        scope: args.scope(),
+                    })
+                    .collect();
+                return Err(Error(Message::Missing(missing)));
+            }
+        };
+        args.remove(ix);
+
+        #[cfg(feature = "autocomplete")]
+        if args.touching_last_remove() {
+            args.clear_comps();
+            for (name, _) in commands.iter() {
+                args.push_command(name, None, &self.help, self.group);
+            }
+            return Err(Error(Message::Missing(Vec::new())));
+        }
+
+        // everything to the right goes to the external command unchanged, this includes "--"
+        let mut rest = Vec::new();
+        let mut skip_next = false;
+        for i in ix + 1..args.scope().end {
+            let arg = match args.get(i) {
+                Some(arg) => arg,
+                None => {
+                    if let Some(Arg::PosWord(w)) = args.items.get(i) {
+                        if w == "--" {
+                            rest.push(w.clone());
+                        }
+                    }
+                    continue;
+                }
+            };
+            match arg {
+                Arg::Short(_, adjacent, os) | Arg::Long(_, adjacent, os) => {
+                    if !os.is_empty() {
+                        rest.push(os.clone());
+                    }
+                    skip_next = *adjacent;
+                    args.remove(i);
+                    continue;
+                }
+                Arg::ArgWord(os) | Arg::Word(os) | Arg::PosWord(os) => {
+                    if !std::mem::take(&mut skip_next) {
+                        rest.push(os.clone());
+                    }
+                }
+            }
+            args.remove(i);
+        }
+
+        Ok(ExternalCommand {
+            name,
+            path,
+            args: rest,
+        })
+    }
+
+    fn meta(&self) -> Meta {
+        let commands = self.commands();
+        let mut alts = commands
+            .iter()
+            .map(|(name, _)| Meta::from(self.item(Some(name))))
+            .collect::<Vec<_>>();
+        match alts.len() {
+            0 => Meta::from(self.item(None)),
+            1 => alts.remove(0),
+            _ => Meta::Or(alts),
+        }
+    }
+}
+
 fn build_flag_parser<T>(present: T, absent: Option<T>, named: NamedArg) -> ParseFlag<T>
 where
     T: Clone + 'static,
@@ -584,6 +985,18 @@ impl<T> ParseFlag<T> {
         self.named.help = Some(help.into());
         self
     }
+
+    /// Add a detailed help message to `flag`
+    ///
+    /// See [`NamedArg::long_help`]
+    #[must_use]
+    pub fn long_help<M>(mut self, help: M) -> Self
+    where
+        M: Into<Doc>,
+    {
+        self.named.long_help = Some(help.into());
+        self
+    }
 }
 
 impl<T> ParseArgument<T> {
@@ -598,6 +1011,23 @@ impl<T> ParseArgument<T> {
         self.named.help = Some(help.into());
         self
     }
+
+    /// Add a detailed help message to an `argument`
+    ///
+    /// See [`NamedArg::long_help`]
+    #[must_use]
+    pub fn long_help<M>(mut self, help: M) -> Self
+    where
+        M: Into<Doc>,
+    {
+        self.named.long_help = Some(help.into());
+        self
+    }
+}
+
+/// Hint set with `value_hint` or the one for a type from the standard library
+fn type_hint<T: 'static>(hint: Option<TypeHint>) -> Option<TypeHint> {
+    hint.or_else(crate::value_hint::builtin::<T>)
 }
 
 fn build_argument<T>(named: NamedArg, metavar: &'static str) -> ParseArgument<T> {
@@ -606,6 +1036,7 @@ fn build_argument<T>(named: NamedArg, metavar: &'static str) -> ParseArgument<T>
         metavar,
         ty: PhantomData,
         adjacent: false,
+        hint: None,
     }
 }
 
@@ -616,6 +1047,7 @@ pub struct ParseArgument<T> {
     named: NamedArg,
     metavar: &'static str,
     adjacent: bool,
+    hint: Option<TypeHint>,
 }
 
 impl<T> ParseArgument<T> {
@@ -634,13 +1066,27 @@ impl<T> ParseArgument<T> {
         self
     }
 
-    fn item(&self) -> Option<Item> {
+    /// Use completion hint from the value type
+    ///
+    /// Types from the standard library such as [`PathBuf`](std::path::PathBuf) or `bool` get
+    /// their hints automatically, see [`ValueHint`] for details
+    #[must_use]
+    pub fn value_hint(mut self) -> Self
+    where
+        T: ValueHint,
+    {
+        self.hint = Some(T::HINT);
+        self
+    }
+
+    fn item(&self, hint: Option<TypeHint>) -> Option<Item> {
         Some(Item::Argument {
             name: ShortLong::try_from(&self.named).ok()?,
             metavar: Metavar(self.metavar),
-            env: self.named.env.first().copied(),
-            help: self.named.help.clone(),
+            env: self.named.env.clone(),
+            help: Doc::with_details(&self.named.help, &self.named.long_help),
             shorts: self.named.short.clone(),
+            hint,
         })
     }
 
@@ -650,6 +1096,15 @@ impl<T> ParseArgument<T> {
                 #[cfg(feature = "autocomplete")]
                 if args.touching_last_remove() {
                     args.push_metavar(self.metavar, &self.named.help, true);
+                    let env = self.named.env.iter();
+                    if let Some((name, val)) = env
+                        .filter_map(|name| Some((name, std::env::var(name).ok()?)))
+                        .next()
+                    {
+                        // value is still current even if it's not shown
+                        let val = Some(val).filter(|_| self.named.complete_env);
+                        args.push_current(val, format!("current: from ${}", name), true);
+                    }
                 }
                 Ok(w)
             }
@@ -666,7 +1121,7 @@ impl<T> ParseArgument<T> {
                     return Ok(val);
                 }
 
-                if let Some(item) = self.item() {
+                if let Some(item) = self.item(self.hint) {
                     let missing = MissingItem {
                         item,
                         position: args.scope().start,
@@ -689,6 +1144,9 @@ where
     <T as std::str::FromStr>::Err: std::fmt::Display,
 {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
+        #[cfg(feature = "autocomplete")]
+        let os = args.with_hint(type_hint::<T>(self.hint), |args| self.take_argument(args))?;
+        #[cfg(not(feature = "autocomplete"))]
         let os = self.take_argument(args)?;
         match parse_os_str::<T>(os) {
             Ok(ok) => Ok(ok),
@@ -697,7 +1155,7 @@ where
     }
 
     fn meta(&self) -> Meta {
-        if let Some(item) = self.item() {
+        if let Some(item) = self.item(type_hint::<T>(self.hint)) {
             Meta::from(item)
         } else {
             Meta::Skip
@@ -709,8 +1167,10 @@ pub(crate) fn build_positional<T>(metavar: &'static str) -> ParsePositional<T> {
     ParsePositional {
         metavar,
         help: None,
+        long_help: None,
         position: Position::Unrestricted,
         ty: PhantomData,
+        hint: None,
     }
 }
 
@@ -722,8 +1182,10 @@ pub(crate) fn build_positional<T>(metavar: &'static str) -> ParsePositional<T> {
 pub struct ParsePositional<T> {
     metavar: &'static str,
     help: Option<Doc>,
+    long_help: Option<Doc>,
     position: Position,
     ty: PhantomData<T>,
+    hint: Option<TypeHint>,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -737,8 +1199,10 @@ impl<T> ParsePositional<T> {
     /// Add a help message to a [`positional`] parser
     ///
     /// `bpaf` converts doc comments and string into help by following those rules:
-    /// 1. Everything up to the first blank line is included into a "short" help message
-    /// 2. Everything is included into a "long" help message
+    /// 1. Everything up to the first blank line is included into a "short" help message,
+    ///    `-h` shows it
+    /// 2. Everything is included into a "long" help message, `--help` and documentation
+    ///    generators show it
     /// 3. `bpaf` preserves linebreaks followed by a line that starts with a space
     /// 4. Linebreaks are removed otherwise
     ///
@@ -755,6 +1219,18 @@ impl<T> ParsePositional<T> {
         self
     }
 
+    /// Add a detailed help message to a [`positional`] parser
+    ///
+    /// See [`NamedArg::long_help`]
+    #[must_use]
+    pub fn long_help<M>(mut self, help: M) -> Self
+    where
+        M: Into<Doc>,
+    {
+        self.long_help = Some(help.into());
+        self
+    }
+
     /// Changes positional parser to be a "strict" positional
     ///
     /// Usually positional items can appear anywhere on a command line:
@@ -794,11 +1270,24 @@ impl<T> ParsePositional<T> {
         self
     }
 
+    /// Use completion hint from the value type
+    ///
+    /// See [`ParseArgument::value_hint`]
+    #[must_use]
+    pub fn value_hint(mut self) -> Self
+    where
+        T: ValueHint,
+    {
+        self.hint = Some(T::HINT);
+        self
+    }
+
     #[inline(always)]
-    fn meta(&self) -> Meta {
+    fn meta(&self, hint: Option<TypeHint>) -> Meta {
         let meta = Meta::from(Item::Positional {
             metavar: Metavar(self.metavar),
-            help: self.help.clone(),
+            help: Doc::with_details(&self.help, &self.long_help),
+            hint,
         });
         match self.position {
             Position::Strict => Meta::Strict(Box::new(meta)),
@@ -855,6 +1344,11 @@ where
     <T as std::str::FromStr>::Err: std::fmt::Display,
 {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
+        #[cfg(feature = "autocomplete")]
+        let os = args.with_hint(type_hint::<T>(self.hint), |args| {
+            parse_pos_word(args, Metavar(self.metavar), &self.help, self.position)
+        })?;
+        #[cfg(not(feature = "autocomplete"))]
         let os = parse_pos_word(args, Metavar(self.metavar), &self.help, self.position)?;
         match parse_os_str::<T>(os) {
             Ok(ok) => Ok(ok),
@@ -864,7 +1358,7 @@ where
 
     #[inline(always)]
     fn meta(&self) -> Meta {
-        self.meta()
+        self.meta(type_hint::<T>(self.hint))
     }
 }
 
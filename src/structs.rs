@@ -7,6 +7,9 @@ use crate::{
 };
 use std::marker::PhantomData;
 
+#[cfg(feature = "autocomplete")]
+use crate::CompletionContext;
+
 /// Parser that substitutes missing value with a function results but not parser
 /// failure, created with [`fallback_with`](Parser::fallback_with).
 pub struct ParseFallbackWith<T, P, F, E> {
@@ -14,6 +17,9 @@ pub struct ParseFallbackWith<T, P, F, E> {
     pub(crate) inner_res: PhantomData<T>,
     pub(crate) fallback: F,
     pub(crate) value_str: String,
+    /// [`Display`](std::fmt::Display) representation of the fallback value for completion
+    #[cfg(feature = "autocomplete")]
+    pub(crate) current: Option<String>,
     pub(crate) err: PhantomData<E>,
 }
 
@@ -24,6 +30,31 @@ where
     E: ToString,
 {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
+        #[cfg(feature = "autocomplete")]
+        if let Some(current) = &self.current {
+            return args.with_current(current, |args| self.eval_fallback(args));
+        }
+        self.eval_fallback(args)
+    }
+
+    fn meta(&self) -> Meta {
+        let m = Meta::Optional(Box::new(self.inner.meta()));
+        if self.value_str.is_empty() {
+            m
+        } else {
+            let buf = Doc::from(self.value_str.as_str());
+            Meta::Suffix(Box::new(m), Box::new(buf))
+        }
+    }
+}
+
+impl<T, P, F, E> ParseFallbackWith<T, P, F, E>
+where
+    P: Parser<T>,
+    F: Fn() -> Result<T, E>,
+    E: ToString,
+{
+    fn eval_fallback(&self, args: &mut State) -> Result<T, Error> {
         let mut clone = args.clone();
         match self.inner.eval(&mut clone) {
             Ok(ok) => {
@@ -44,16 +75,6 @@ where
             }
         }
     }
-
-    fn meta(&self) -> Meta {
-        let m = Meta::Optional(Box::new(self.inner.meta()));
-        if self.value_str.is_empty() {
-            m
-        } else {
-            let buf = Doc::from(self.value_str.as_str());
-            Meta::Suffix(Box::new(m), Box::new(buf))
-        }
-    }
 }
 
 /// Parser with attached message to several fields, created with [`group_help`](Parser::group_help).
@@ -479,6 +500,9 @@ pub struct ParseFallback<P, T> {
     pub(crate) inner: P,
     pub(crate) value: T,
     pub(crate) value_str: String,
+    /// [`Display`](std::fmt::Display) representation of the fallback value for completion
+    #[cfg(feature = "autocomplete")]
+    pub(crate) current: Option<String>,
 }
 
 impl<P, T> Parser<T> for ParseFallback<P, T>
@@ -487,6 +511,30 @@ where
     T: Clone,
 {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
+        #[cfg(feature = "autocomplete")]
+        if let Some(current) = &self.current {
+            return args.with_current(current, |args| self.eval_fallback(args));
+        }
+        self.eval_fallback(args)
+    }
+
+    fn meta(&self) -> Meta {
+        let m = Meta::Optional(Box::new(self.inner.meta()));
+        if self.value_str.is_empty() {
+            m
+        } else {
+            let buf = Doc::from(self.value_str.as_str());
+            Meta::Suffix(Box::new(m), Box::new(buf))
+        }
+    }
+}
+
+impl<P, T> ParseFallback<P, T>
+where
+    P: Parser<T>,
+    T: Clone,
+{
+    fn eval_fallback(&self, args: &mut State) -> Result<T, Error> {
         let mut clone = args.clone();
         match self.inner.eval(&mut clone) {
             Ok(ok) => {
@@ -504,16 +552,6 @@ where
             }
         }
     }
-
-    fn meta(&self) -> Meta {
-        let m = Meta::Optional(Box::new(self.inner.meta()));
-        if self.value_str.is_empty() {
-            m
-        } else {
-            let buf = Doc::from(self.value_str.as_str());
-            Meta::Suffix(Box::new(m), Box::new(buf))
-        }
-    }
 }
 
 impl<P, T: std::fmt::Display> ParseFallback<P, T> {
@@ -524,6 +562,10 @@ impl<P, T: std::fmt::Display> ParseFallback<P, T> {
     #[must_use]
     pub fn display_fallback(mut self) -> Self {
         self.value_str = format!("[default: {}]", self.value);
+        #[cfg(feature = "autocomplete")]
+        {
+            self.current = Some(self.value.to_string());
+        }
         self
     }
 }
@@ -554,6 +596,10 @@ where
     pub fn display_fallback(mut self) -> Self {
         if let Ok(val) = (self.fallback)() {
             self.value_str = format!("[default: {}]", val);
+            #[cfg(feature = "autocomplete")]
+            {
+                self.current = Some(val.to_string());
+            }
         }
         self
     }
@@ -966,6 +1012,69 @@ impl<T> ParseCon<T> {
     }
 }
 
+/// Replace metavars added by `inner` with values generated by `op`
+#[cfg(feature = "autocomplete")]
+pub(crate) fn eval_comp<P, T, M, F>(
+    inner: &P,
+    group: &Option<String>,
+    op: F,
+    args: &mut State,
+) -> Result<T, Error>
+where
+    P: Parser<T>,
+    M: Into<String>,
+    F: Fn(&T, &CompletionContext) -> Vec<(M, Option<M>)>,
+{
+    // stash old
+    let mut comp_items = Vec::new();
+    args.swap_comps_with(&mut comp_items);
+
+    let res = inner.eval(args);
+
+    // restore old, now metavars added by inner parser, if any, are in comp_items
+    args.swap_comps_with(&mut comp_items);
+
+    if let Some(comp) = &mut args.comp_mut() {
+        if res.is_err() {
+            comp.extend_comps(comp_items);
+            return res;
+        }
+    }
+
+    let res = res?;
+
+    // completion function generates suggestions based on the parsed inner value, for
+    // that `res` must contain a parsed value
+    let depth = args.depth();
+    let ctx = CompletionContext::new(args);
+    if let Some(comp) = &mut args.comp_mut() {
+        for mut ci in comp_items {
+            let is_meta = ci.is_metavar();
+            if let Some(is_arg) = is_meta {
+                let suggestions = op(&res, &ctx);
+                // strip metavar when completion makes a single good suggestion
+                if suggestions.len() != 1 {
+                    ci.clear_hint();
+                    comp.push_comp(ci);
+                }
+                for (replacement, description) in suggestions {
+                    let group = group.clone();
+                    comp.push_value(
+                        replacement.into(),
+                        description.map(Into::into),
+                        group,
+                        depth,
+                        is_arg,
+                    );
+                }
+            } else {
+                comp.push_comp(ci);
+            }
+        }
+    }
+    Ok(res)
+}
+
 /// Parser that replaces metavar placeholders with actual info in shell completion
 #[cfg(feature = "autocomplete")]
 pub struct ParseComp<P, F> {
@@ -992,56 +1101,45 @@ where
     F: Fn(&T) -> Vec<(M, Option<M>)>,
 {
     fn eval(&self, args: &mut State) -> Result<T, Error> {
-        // stash old
-        let mut comp_items = Vec::new();
-        args.swap_comps_with(&mut comp_items);
+        eval_comp(&self.inner, &self.group, |t, _| (self.op)(t), args)
+    }
 
-        let res = self.inner.eval(args);
+    fn meta(&self) -> Meta {
+        Meta::Comp(Box::new(self.inner.meta()), None)
+    }
+}
 
-        // restore old, now metavars added by inner parser, if any, are in comp_items
-        args.swap_comps_with(&mut comp_items);
+/// Parser that replaces metavar placeholders with values that depend on other arguments
+#[cfg(feature = "autocomplete")]
+pub struct ParseCompWith<P, F> {
+    pub(crate) inner: P,
+    pub(crate) op: F,
+    pub(crate) group: Option<String>,
+}
 
-        if let Some(comp) = &mut args.comp_mut() {
-            if res.is_err() {
-                comp.extend_comps(comp_items);
-                return res;
-            }
-        }
+#[cfg(feature = "autocomplete")]
+impl<P, F> ParseCompWith<P, F> {
+    #[must_use]
+    /// Attach group name to parsed values
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+}
 
-        let res = res?;
-
-        // completion function generates suggestions based on the parsed inner value, for
-        // that `res` must contain a parsed value
-        let depth = args.depth();
-        if let Some(comp) = &mut args.comp_mut() {
-            for ci in comp_items {
-                let is_meta = ci.is_metavar();
-                if let Some(is_arg) = is_meta {
-                    let suggestions = (self.op)(&res);
-                    // strip metavar when completion makes a single good suggestion
-                    if suggestions.len() != 1 {
-                        comp.push_comp(ci);
-                    }
-                    for (replacement, description) in suggestions {
-                        let group = self.group.clone();
-                        comp.push_value(
-                            replacement.into(),
-                            description.map(Into::into),
-                            group,
-                            depth,
-                            is_arg,
-                        );
-                    }
-                } else {
-                    comp.push_comp(ci);
-                }
-            }
-        }
-        Ok(res)
+#[cfg(feature = "autocomplete")]
+impl<P, T, F, M> Parser<T> for ParseCompWith<P, F>
+where
+    P: Parser<T> + Sized,
+    M: Into<String>,
+    F: Fn(&T, &CompletionContext) -> Vec<(M, Option<M>)>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        eval_comp(&self.inner, &self.group, &self.op, args)
     }
 
     fn meta(&self) -> Meta {
-        self.inner.meta()
+        Meta::Comp(Box::new(self.inner.meta()), None)
     }
 }
 
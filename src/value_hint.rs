@@ -0,0 +1,98 @@
+//! Default metavariables and completion hints derived from the value type
+//!
+//! [`argument`](crate::NamedArg::argument) and [`positional`](crate::positional) use hints for
+//! types from the standard library automatically, other types can opt in with
+//! [`value_hint`](crate::parsers::ParseArgument::value_hint).
+
+use std::{ffi::OsString, path::PathBuf};
+
+/// What shell completion should offer for a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TypeHint {
+    /// Nothing in particular, completion shows the metavariable
+    Any,
+    /// File names
+    File,
+    /// Directory names
+    Dir,
+    /// One of the listed values, with optional descriptions
+    OneOf(&'static [(&'static str, Option<&'static str>)]),
+}
+
+/// Metavariable and completion hint for values of a type
+///
+/// Implemented for [`PathBuf`], [`OsString`], numeric types and `bool`, implement it for your
+/// own types such as enums to get completion for their values. Only [`PathBuf`] completes file
+/// names, [`OsString`] values are not necessarily paths.
+///
+/// ```rust
+/// # use bpaf::*;
+/// #[derive(Debug, Clone)]
+/// enum Level {
+///     Low,
+///     High,
+/// }
+///
+/// # impl std::str::FromStr for Level {
+/// #     type Err = String;
+/// #     fn from_str(s: &str) -> Result<Self, String> {
+/// #         match s {
+/// #             "low" => Ok(Level::Low),
+/// #             "high" => Ok(Level::High),
+/// #             _ => Err("expected low or high".to_owned()),
+/// #         }
+/// #     }
+/// # }
+/// impl ValueHint for Level {
+///     const METAVAR: &'static str = "LEVEL";
+///     const HINT: TypeHint = TypeHint::OneOf(&[("low", None), ("high", Some("Maximum effort"))]);
+/// }
+///
+/// fn level() -> impl Parser<Level> {
+///     long("level").argument::<Level>(Level::METAVAR).value_hint()
+/// }
+/// ```
+pub trait ValueHint {
+    /// Metavariable to use when none is given explicitly
+    const METAVAR: &'static str = "ARG";
+    /// What shell completion should offer
+    const HINT: TypeHint = TypeHint::Any;
+}
+
+impl ValueHint for PathBuf {
+    const METAVAR: &'static str = "PATH";
+    const HINT: TypeHint = TypeHint::File;
+}
+
+impl ValueHint for OsString {}
+
+impl ValueHint for bool {
+    const METAVAR: &'static str = "BOOL";
+    const HINT: TypeHint = TypeHint::OneOf(&[("true", None), ("false", None)]);
+}
+
+macro_rules! numeric_hint {
+    ($($ty:ty),*) => {
+        $(impl ValueHint for $ty {
+            const METAVAR: &'static str = "NUM";
+        })*
+
+        /// Hint for types from the standard library, used without a [`ValueHint`] bound
+        pub(crate) fn builtin<T: 'static>() -> Option<TypeHint> {
+            use std::any::TypeId;
+            let ty = TypeId::of::<T>();
+            if ty == TypeId::of::<PathBuf>() {
+                Some(PathBuf::HINT)
+            } else if ty == TypeId::of::<bool>() {
+                Some(bool::HINT)
+            } $(else if ty == TypeId::of::<$ty>() {
+                Some(<$ty>::HINT)
+            })* else {
+                None
+            }
+        }
+    };
+}
+
+numeric_hint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
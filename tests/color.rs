@@ -0,0 +1,79 @@
+use bpaf::{batteries::color_choice, doc::*, *};
+
+#[test]
+fn themed_help() {
+    let theme = Theme::monochrome()
+        .style(Style::Literal, "1")
+        .style(Style::Metavar, "4");
+    let parser = short('n')
+        .help("Number of things")
+        .argument::<usize>("N")
+        .to_options();
+    let r = parser.run_inner(&["--help"]).unwrap_err();
+    let doc = match r {
+        ParseFailure::Stdout(doc, ..) => doc,
+        _ => panic!("expected help"),
+    };
+
+    let expected = "\
+Usage: \x1b[1m-n\x1b[0m=\x1b[4mN\x1b[0m
+
+Available options:
+    \x1b[1m-n\x1b[0m=\x1b[4mN\x1b[0m        Number of things
+    \x1b[1m-h\x1b[0m, \x1b[1m--help\x1b[0m  Prints help information
+";
+    assert_eq!(doc.themed(true, &theme), expected);
+    assert_eq!(doc.themed(true, &Theme::monochrome()), doc.monochrome(true));
+}
+
+#[test]
+fn themed_error() {
+    let theme = Theme::bright().style(Style::Invalid, "4;31");
+    let parser = short('n').argument::<usize>("N").to_options();
+    let r = parser.run_inner(&["-n", "x"]).unwrap_err();
+    let doc = match r {
+        ParseFailure::Stderr(doc, _) => doc,
+        _ => panic!("expected error"),
+    };
+    let expected = "couldn't parse \x1b[4;31mx\x1b[0m: invalid digit found in string";
+    assert_eq!(doc.themed(true, &theme), expected);
+}
+
+#[test]
+fn color_choice_battery() {
+    let parser = color_choice().to_options();
+
+    let r = parser.run_inner(&["--color=always"]).unwrap();
+    assert_eq!(r, ColorChoice::Always);
+    assert_eq!(ColorChoice::global(), ColorChoice::Always);
+    assert!(ColorChoice::enabled());
+
+    let r = parser.run_inner(&["--color", "never"]).unwrap();
+    assert_eq!(r, ColorChoice::Never);
+    assert!(!ColorChoice::enabled());
+
+    // absent option keeps the previous choice
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, ColorChoice::Never);
+    assert_eq!(ColorChoice::global(), ColorChoice::Never);
+
+    let r = parser
+        .run_inner(&["--color=sometimes"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "couldn't parse `sometimes`: expected one of `auto`, `always` or `never`, got `sometimes`"
+    );
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [--color=WHEN]
+
+Available options:
+        --color=WHEN  When to use colors: auto, always or never
+    -h, --help        Prints help information
+";
+    assert_eq!(r, expected);
+    ColorChoice::Auto.set_global();
+}
@@ -0,0 +1,32 @@
+//! Color choice and environment variables are process wide, this lives in a separate binary
+use bpaf::doc::ColorChoice;
+
+fn with_env(env: &[(&str, &str)]) -> bool {
+    for var in ["NO_COLOR", "CLICOLOR", "CLICOLOR_FORCE"] {
+        std::env::remove_var(var);
+    }
+    for (var, val) in env {
+        std::env::set_var(var, val);
+    }
+    ColorChoice::enabled()
+}
+
+#[test]
+fn color_environment() {
+    ColorChoice::Auto.set_global();
+    assert!(with_env(&[("CLICOLOR_FORCE", "1")]));
+    assert!(!with_env(&[("CLICOLOR", "0")]));
+    assert!(with_env(&[("CLICOLOR", "0"), ("CLICOLOR_FORCE", "1")]));
+    assert!(!with_env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]));
+    // empty NO_COLOR is the same as unset
+    assert!(with_env(&[("NO_COLOR", ""), ("CLICOLOR_FORCE", "1")]));
+
+    // explicit choice overrides the environment
+    ColorChoice::Always.set_global();
+    assert!(with_env(&[("NO_COLOR", "1")]));
+    ColorChoice::Never.set_global();
+    assert!(!with_env(&[("CLICOLOR_FORCE", "1")]));
+
+    ColorChoice::Auto.set_global();
+    with_env(&[]);
+}
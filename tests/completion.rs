@@ -1583,3 +1583,1225 @@ fn positionals_with_no_completions_are_not_duplicated() {
 \tBETA\t\tBeta argument\n\n"
     );
 }
+
+#[test]
+fn command_groups_completion() {
+    let commit = pure(())
+        .to_options()
+        .descr("Record changes")
+        .command("commit")
+        .group("Porcelain commands");
+    let cat_file = pure(())
+        .to_options()
+        .descr("Provide content")
+        .command("cat-file")
+        .group("Plumbing commands");
+    let check = pure(()).to_options().command("check");
+    let parser = construct!([commit, cat_file, check]).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["c"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+commit\tcommit\tPorcelain commands\tRecord changes
+cat-file\tcat-file\tPlumbing commands\tProvide content
+check\tcheck\t\t\n\n"
+    );
+}
+
+#[test]
+fn help_subcommand_completion() {
+    let add = pure(()).to_options().descr("Add a remote").command("add");
+    let remove = pure(())
+        .to_options()
+        .descr("Remove a remote")
+        .command("remove");
+    let remote = construct!([add, remove])
+        .to_options()
+        .descr("Manage remotes")
+        .command("remote");
+    let build = pure(()).to_options().descr("Build").command("build");
+    let parser = construct!([remote, build]).to_options().help_command();
+
+    let r = parser
+        .run_inner(Args::from(&[""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+remote\tremote\t\tManage remotes
+build\tbuild\t\tBuild
+help\thelp\t\tPrints help for a command\n\n"
+    );
+
+    let r = parser
+        .run_inner(Args::from(&["he"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "help");
+
+    let r = parser
+        .run_inner(Args::from(&["help", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "remote\tremote\t\tManage remotes\nbuild\tbuild\t\tBuild\n\n"
+    );
+
+    let r = parser
+        .run_inner(Args::from(&["help", "remote", "re"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "remove");
+}
+
+#[test]
+fn powershell_protocol() {
+    let verbose = short('v').long("verbose").help("Be verbose").switch();
+    let name = long("name").argument::<String>("NAME");
+    let file = positional::<String>("FILE").complete_shell(ShellComp::File { mask: Some("*.rs") });
+    let parser = construct!(verbose, name, file).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["-"]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Files\t-\t*.rs\t
+ParameterName\t--verbose\t--verbose\tBe verbose
+ParameterName\t--name\t--name=NAME\t--name=NAME
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(Args::from(&["--name", ""]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "Text\t\tNAME\tNAME\n";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(Args::from(&["--name", "x", "src/"]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "Files\tsrc/\t*.rs\t\n";
+    assert_eq!(r, expected);
+
+    // quotes are data, tabs and newlines would break the format
+    let r = parser
+        .run_inner(Args::from(&["--name", "it\u{2019}s';\tx"]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "Text\tit\u{2019}s'; x\tNAME\tNAME\n");
+
+    // raw values for other shells fall back to file names
+    let raw = ShellComp::Raw {
+        bash: "",
+        zsh: "",
+        fish: "",
+        elvish: "",
+    };
+    let parser = positional::<String>("FILE")
+        .complete_shell(raw)
+        .to_options();
+    let r = parser
+        .run_inner(Args::from(&["sr"]).set_comp(10))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "Files\tsr\t\t\n");
+}
+
+#[test]
+fn nushell_protocol() {
+    let verbose = short('v')
+        .long("verbose")
+        .help("Be \"very\" verbose")
+        .switch();
+    let name = long("name").argument::<String>("NAME");
+    let parser = construct!(verbose, name).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["-"]).set_comp(11))
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = r#"[
+{"value": "--verbose", "description": "Be \"very\" verbose"},
+{"value": "--name"}
+]
+"#;
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(Args::from(&["--name", ""]).set_comp(11))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "[\n{\"value\": \"\", \"description\": \"NAME\"}\n]\n");
+
+    let r = parser
+        .run_inner(Args::from(&["--name", "x"]).set_comp(11))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "[\n{\"value\": \"x\", \"description\": \"NAME\"}\n]\n");
+}
+
+#[test]
+fn nushell_file_masks() {
+    let dir = std::env::temp_dir().join(format!("bpaf_nushell_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::create_dir_all(dir.join("srv.rs")).unwrap();
+    for file in ["main.rs", "Cargo.toml", "readme.md", "source.rs"] {
+        std::fs::write(dir.join(file), "").unwrap();
+    }
+    let dir_str = format!("{}/", dir.display());
+
+    let files = |mask| {
+        let parser = positional::<String>("FILE")
+            .complete_shell(ShellComp::File { mask })
+            .to_options();
+        let dirs = positional::<String>("DIR")
+            .complete_shell(ShellComp::Dir { mask })
+            .to_options();
+        let lit = [format!("{}s", dir_str)];
+        let f = parser
+            .run_inner(Args::from(&lit[..]).set_comp(11))
+            .unwrap_err()
+            .unwrap_stdout();
+        let d = dirs
+            .run_inner(Args::from(&lit[..]).set_comp(11))
+            .unwrap_err()
+            .unwrap_stdout();
+        (f.replace(&dir_str, ""), d.replace(&dir_str, ""))
+    };
+
+    let (f, d) = files(None);
+    assert_eq!(
+        f,
+        "[\n{\"value\": \"source.rs\"},\n{\"value\": \"src/\"},\n{\"value\": \"srv.rs/\"}\n]\n"
+    );
+    assert_eq!(
+        d,
+        "[\n{\"value\": \"src/\"},\n{\"value\": \"srv.rs/\"}\n]\n"
+    );
+
+    let (f, d) = files(Some("*.(rs|toml)"));
+    assert_eq!(
+        f,
+        "[\n{\"value\": \"source.rs\"},\n{\"value\": \"src/\"},\n{\"value\": \"srv.rs/\"}\n]\n"
+    );
+    assert_eq!(d, "[\n{\"value\": \"srv.rs/\"}\n]\n");
+
+    let (f, _) = files(Some("*.md"));
+    assert_eq!(
+        f,
+        "[\n{\"value\": \"src/\"},\n{\"value\": \"srv.rs/\"}\n]\n"
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+type StaticOpts = (bool, String, (String, String), Vec<String>);
+
+fn static_parser() -> OptionParser<StaticOpts> {
+    fn branches(_: &String) -> Vec<(String, Option<String>)> {
+        Vec::new()
+    }
+    let verbose = short('v').long("verbose").help("Be verbose").switch();
+    let config = long("config")
+        .help("Config file")
+        .argument::<String>("FILE")
+        .complete_shell(ShellComp::File {
+            mask: Some("*.(toml|json)"),
+        });
+    let branch = long("branch")
+        .argument::<String>("BRANCH")
+        .complete(branches);
+    let name = positional::<String>("NAME").complete_shell(ShellComp::Dir { mask: None });
+    let add = construct!(branch, name)
+        .to_options()
+        .descr("Add a remote")
+        .command("add")
+        .short('a');
+    let files = positional::<String>("FILE")
+        .complete_shell(ShellComp::File { mask: None })
+        .many();
+    construct!(verbose, config, add, files).to_options()
+}
+
+#[test]
+fn static_bash_script() {
+    let r = static_parser().render_bash_completion("app");
+    let expected = r##"_bpaf_app_add() {
+    mapfile -t -O "${#COMPREPLY[@]}" COMPREPLY < <(compgen "$@" -- "$cur")
+}
+_bpaf_app_dynamic() {
+    local -a args=()
+    local w glue=''
+    for w in "${COMP_WORDS[@]:1:COMP_CWORD}"; do
+        if [[ ($w == = || -n $glue) && ${#args[@]} -gt 0 ]]; then
+            args[${#args[@]} - 1]+="$w"
+        else
+            args+=("$w")
+        fi
+        [[ $w == = ]] && glue=1 || glue=''
+    done
+    source <("${COMP_WORDS[0]}" --bpaf-complete-rev=8 "${args[@]}")
+}
+_bpaf_static_app() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" cmd='app' arg='' pos=0 i w
+    COMPREPLY=()
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        w="${COMP_WORDS[i]}"
+        if [[ -n $arg ]]; then
+            [[ $w == = ]] || arg=''
+            continue
+        fi
+        case "$cmd:$w" in
+            'app:add'|'app:a') cmd='app add'; pos=0 ;;
+            'app:--config') arg='app --config' ;;
+            'app add:--branch') arg='app add --branch' ;;
+            *) [[ $w == -* ]] || ((pos++)) ;;
+        esac
+    done
+    [[ -n $arg && $cur == = ]] && cur=''
+    case "$arg" in
+        'app --config') compopt -o filenames 2>/dev/null; _bpaf_app_add -d; _bpaf_app_add -f -X '!*.toml'; _bpaf_app_add -f -X '!*.json'; return ;;
+        'app add --branch') _bpaf_app_dynamic; return ;;
+    esac
+    case "$cmd" in
+        'app')
+            if [[ $cur == -* ]]; then
+                _bpaf_app_add -W '--verbose --config --help'
+                return
+            fi
+            _bpaf_app_add -W 'add'
+            case "$pos" in
+                *) compopt -o filenames 2>/dev/null; _bpaf_app_add -f ;;
+            esac
+            ;;
+        'app add')
+            if [[ $cur == -* ]]; then
+                _bpaf_app_add -W '--branch --help'
+                return
+            fi
+            case "$pos" in
+                0) compopt -o filenames 2>/dev/null; _bpaf_app_add -d ;;
+            esac
+            ;;
+    esac
+}
+complete -F _bpaf_static_app 'app'
+"##;
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn static_zsh_script() {
+    let r = static_parser().render_zsh_completion("app");
+    let expected = r##"#compdef app
+local cur=${words[CURRENT]} cmd='app' arg='' eq='' pos=0 i w
+local -a flags commands values
+for ((i = 2; i < CURRENT; i++)); do
+    w=${words[i]}
+    if [[ -n $arg ]]; then
+        arg=''
+        continue
+    fi
+    case "$cmd:$w" in
+        'app:add'|'app:a') cmd='app add'; pos=0 ;;
+        'app:--config') arg='app --config' ;;
+        'app add:--branch') arg='app add --branch' ;;
+        *) [[ $w == -* ]] || ((pos++)) ;;
+    esac
+done
+if [[ -z $arg && $cur == --*=* ]]; then
+    case "$cmd:${cur%%=*}" in
+        'app:--config') arg='app --config' ;;
+        'app add:--branch') arg='app add --branch' ;;
+    esac
+    [[ -n $arg ]] && eq=1
+fi
+case $arg in
+    'app --config') [[ -n $eq ]] && compset -P 1 '*='; _files -g '*.(toml|json)'; return ;;
+    'app add --branch') source <(${words[1]} --bpaf-complete-rev=7 "${(@)words[2,CURRENT]}"); return ;;
+esac
+case $cmd in
+    'app')
+        flags=('--verbose:Be verbose' '--config:Config file' '--help:Prints help information')
+        if [[ $cur == -* ]]; then
+            _describe option flags
+            return
+        fi
+        commands=('add:Add a remote')
+        _describe command commands
+        case $pos in
+            *) _files ;;
+        esac
+        ;;
+    'app add')
+        flags=('--branch' '--help:Prints help information')
+        if [[ $cur == -* ]]; then
+            _describe option flags
+            return
+        fi
+        case $pos in
+            0) _files -/ ;;
+        esac
+        ;;
+esac
+"##;
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn static_fish_script() {
+    let r = static_parser().render_fish_completion("app");
+    let expected = r##"function __bpaf_static_app
+    set -l words (commandline --tokenize --cut-at-cursor --current-process)
+    set -l cur (commandline --current-token)
+    set -l cmd 'app'
+    set -l arg ''
+    set -l pos 0
+    set -l pre ''
+    for w in $words[2..-1]
+        if test -n "$arg"
+            set arg ''
+            continue
+        end
+        switch "$cmd:$w"
+            case 'app:add' 'app:a'
+                set cmd 'app add'
+                set pos 0
+            case 'app:--config'
+                set arg 'app --config'
+            case 'app add:--branch'
+                set arg 'app add --branch'
+            case '*'
+                string match -q -- '-*' $w; or set pos (math $pos + 1)
+        end
+    end
+    if test -z "$arg"; and string match -q -- '--*=*' "$cur"
+        set -l kv (string split -m 1 -- = "$cur")
+        switch "$cmd:$kv[1]"
+            case 'app:--config'
+                set arg 'app --config'
+            case 'app add:--branch'
+                set arg 'app add --branch'
+        end
+        if test -n "$arg"
+            set pre "$kv[1]="
+            set cur "$kv[2]"
+        end
+    end
+    switch "$arg"
+        case 'app --config'
+            for f in "$cur"*
+                if test -d $f
+                    echo $pre$f/
+                else if string match -qr -- '(^|/)[^/]*\\.(toml|json)$' $f
+                    echo $pre$f
+                end
+            end
+            return
+        case 'app add --branch'
+            $words[1] --bpaf-complete-rev=1 $words[2..-1] "$pre$cur" | string match -rv '^(\t.*)?$'
+            return
+    end
+    switch "$cmd"
+        case 'app'
+            if string match -q -- '-*' "$cur"
+                printf '%s\t%s\n' '--verbose' 'Be verbose'
+                printf '%s\t%s\n' '--config' 'Config file'
+                printf '%s\t%s\n' '--help' 'Prints help information'
+                return
+            end
+            printf '%s\t%s\n' 'add' 'Add a remote'
+            switch $pos
+                case '*'
+                    for f in "$cur"*
+                        if test -d $f
+                            echo $pre$f/
+                        else
+                            echo $pre$f
+                        end
+                    end
+            end
+        case 'app add'
+            if string match -q -- '-*' "$cur"
+                echo '--branch'
+                printf '%s\t%s\n' '--help' 'Prints help information'
+                return
+            end
+            switch $pos
+                case '0'
+                    for f in "$cur"*
+                        if test -d $f
+                            echo $pre$f/
+                        end
+                    end
+            end
+    end
+end
+complete -c 'app' -f -a '(__bpaf_static_app)'
+"##;
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn static_scripts_quote_app_name() {
+    let parser = static_parser();
+    let r = parser.render_bash_completion("my app");
+    assert!(r.ends_with("complete -F _bpaf_static_my_app 'my app'\n"));
+    let r = parser.render_fish_completion("it's");
+    assert!(r.ends_with("complete -c 'it\\'s' -f -a '(__bpaf_static_it_s)'\n"));
+}
+
+#[test]
+fn fuzzy_matcher() {
+    let cmd = |name: &'static str| pure(()).to_options().descr(name).command(name);
+    let verbose = long("verbose").help("Be verbose").switch();
+    let debug = long("debug-level").argument::<usize>("N");
+    let (status, stash, store) = (cmd("status"), cmd("stash"), cmd("Store"));
+    let (restore, remote) = (cmd("restore"), cmd("remote-set"));
+    let commands = construct!([status, stash, store, restore, remote]);
+    let parser = construct!(verbose, debug, commands)
+        .to_options()
+        .completion_matcher(CompletionMatcher::Fuzzy);
+
+    // prefix first, then case insensitive prefix, then substring and subsequence
+    let r = parser
+        .run_inner(Args::from(&["st"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+status\tstatus\t\tstatus
+stash\tstash\t\tstash
+Store\tStore\t\tStore
+restore\trestore\t\trestore
+remote-set\tremote-set\t\tremote-set\n\n"
+    );
+
+    let r = parser
+        .run_inner(Args::from(&["rst"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "restore\trestore\t\trestore\nremote-set\tremote-set\t\tremote-set\n\n"
+    );
+
+    // substring match for long names
+    let r = parser
+        .run_inner(Args::from(&["--level"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--debug-level");
+
+    // typo fallback when nothing else matches
+    let r = parser
+        .run_inner(Args::from(&["sattus"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "status");
+
+    let r = parser
+        .run_inner(Args::from(&["--vrebose"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--verbose");
+
+    // zsh must not filter candidates by prefix
+    let r = parser
+        .run_inner(Args::from(&["rst"]).set_comp(7))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(
+        r.contains("compadd -U -l -V nosort -d descr -- 'restore'"),
+        "{}",
+        r
+    );
+
+    // default matcher is unchanged
+    let (status, restore) = (cmd("status"), cmd("restore"));
+    let parser = construct!([status, restore]).to_options();
+    let r = parser
+        .run_inner(Args::from(&["st"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "status");
+}
+
+#[test]
+fn attached_values() {
+    fn targets(input: &String) -> Vec<(&'static str, Option<&'static str>)> {
+        ["x86_64", "x86", "arm"]
+            .iter()
+            .filter(|t| t.starts_with(input.as_str()))
+            .map(|t| (*t, None))
+            .collect()
+    }
+    let verbose = short('v').switch();
+    let target = short('p')
+        .long("target")
+        .argument::<String>("T")
+        .complete(targets);
+    let config = short('c')
+        .long("config")
+        .argument::<String>("FILE")
+        .complete_shell(ShellComp::File { mask: None });
+    let parser = construct!(verbose, target, config).to_options();
+    let run = |args: &[&str], rev: usize| {
+        parser
+            .run_inner(Args::from(args).set_comp(rev).set_name("app"))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+
+    // values keep the part of the word before them as typed
+    assert_eq!(run(&["--target=x86_"], 0), "--target=x86_64");
+    assert_eq!(run(&["-px86_"], 0), "-px86_64");
+    assert_eq!(run(&["-p=x86_"], 0), "-p=x86_64");
+    assert_eq!(run(&["-vpx86_"], 0), "-vpx86_64");
+    assert_eq!(run(&["--target", "x86_"], 0), "x86_64");
+    assert_eq!(
+        run(&["--target="], 0),
+        "\tT\t\t\n--target=x86_64\tx86_64\t\t\n--target=x86\tx86\t\t\n--target=arm\tarm\t\t\n\n"
+    );
+
+    // zsh and nushell replace the whole word
+    assert_eq!(run(&["--target=a"], 7), "compadd -- '--target=arm'\n");
+    assert_eq!(
+        run(&["--target=a"], 11),
+        "[\n{\"value\": \"--target=arm\"}\n]\n"
+    );
+
+    // bash replaces only the part after "=", but the whole "-kval" word
+    assert_eq!(run(&["--target=a"], 8), "COMPREPLY+=( 'arm' )\n\n");
+    assert_eq!(run(&["-p=a"], 8), "COMPREPLY+=( 'arm' )\n\n");
+    assert_eq!(run(&["-pa"], 8), "COMPREPLY+=( '-parm' )\n\n");
+    assert_eq!(
+        run(&["-px"], 8),
+        "COMPREPLY+=('T')\nCOMPREPLY+=('-px86_64')\nCOMPREPLY+=('-px86')\n"
+    );
+
+    // values that look like flags are still values
+    assert_eq!(
+        run(&["--target=a"], 9),
+        "complete -c app -f -a --target=arm \n"
+    );
+    assert_eq!(
+        run(&["--target=a"], 10),
+        "ParameterValue\t--target=arm\tarm\tarm\n"
+    );
+
+    // file names are completed after the prefix
+    assert_eq!(
+        run(&["--config=sr"], 7),
+        "compset -P '--config='\n_files\nlocal -a descr\n"
+    );
+    assert_eq!(run(&["-csr"], 10), "Files\tsr\t\t-c\n");
+}
+
+#[test]
+fn fig_spec() {
+    let r = static_parser().render_fig_spec("app");
+    let expected = r##"const bpafGenerator: Fig.Generator = {
+  script: (tokens) => [tokens[0], "--bpaf-complete-rev=1", ...tokens.slice(1)],
+  postProcess: (out) =>
+    out
+      .split("\n")
+      .filter((line) => !(line === "" || line.startsWith("\t")))
+      .map((line) => {
+        const [name, description] = line.split("\t");
+        return { name, description };
+      }),
+};
+
+const completionSpec: Fig.Spec = {
+  name: "app",
+  options: [
+    {
+      name: ["--verbose", "-v"],
+      description: "Be verbose",
+    },
+    {
+      name: "--config",
+      description: "Config file",
+      args: {
+        name: "FILE",
+        generators: {
+          template: "filepaths",
+          filterTemplateSuggestions: (paths) =>
+            paths.filter((p) => p.type === "folder" || new RegExp("(^|/)[^/]*\\.(toml|json)$").test(p.name.replace(/\/$/, ""))),
+        },
+      },
+    },
+    {
+      name: ["--help", "-h"],
+      description: "Prints help information",
+    },
+  ],
+  args: [
+    {
+      name: "FILE",
+      isVariadic: true,
+      template: "filepaths",
+    },
+  ],
+  subcommands: [
+    {
+      name: ["add", "a"],
+      description: "Add a remote",
+      options: [
+        {
+          name: "--branch",
+          args: {
+            name: "BRANCH",
+            generators: bpafGenerator,
+          },
+        },
+        {
+          name: ["--help", "-h"],
+          description: "Prints help information",
+        },
+      ],
+      args: [
+        {
+          name: "NAME",
+          template: "folders",
+        },
+      ],
+    },
+  ],
+};
+
+export default completionSpec;
+"##;
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn carapace_spec() {
+    let r = static_parser().render_carapace_spec("app");
+    let expected = r##"name: "app"
+flags:
+  "-v, --verbose": "Be verbose"
+  "--config=": "Config file"
+  "-h, --help": "Prints help information"
+completion:
+  flag:
+    "config": ["$files([.toml, .json])"]
+  positionalany: ["$files"]
+commands:
+  - name: "add"
+    aliases: ["a"]
+    description: "Add a remote"
+    flags:
+      "--branch=": ""
+      "-h, --help": "Prints help information"
+    completion:
+      flag:
+        "branch": ["$('app' --bpaf-complete-rev=1 'add' '--branch' \"$C_VALUE\" | awk -F '\\t' '$1 != \"\"')"]
+      positional:
+        - ["$directories"]
+"##;
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn spec_generator_hook() {
+    let generator = |words: &[&str]| format!("{:?}", words.join(" "));
+    let r = static_parser().render_carapace_spec_with("app", generator);
+    assert!(r.contains(r#""branch": ["\"app add --branch\""]"#), "{}", r);
+
+    let generator = |words: &[&str]| format!("gen({})", words.len());
+    let r = static_parser().render_fig_spec_with("app", generator);
+    assert!(!r.contains("bpafGenerator"));
+    assert!(r.contains("generators: gen(3),"), "{}", r);
+}
+
+#[test]
+fn in_process_completions() {
+    let verbose = short('v')
+        .long("verbose")
+        .help("Be verbose, \\ and\ttabs")
+        .switch();
+    let target = long("target")
+        .argument::<String>("T")
+        .complete(|input: &String| {
+            ["x86_64", "arm"]
+                .iter()
+                .filter(|t| t.starts_with(input.as_str()))
+                .map(|t| (*t, Some("architecture")))
+                .collect::<Vec<_>>()
+        });
+    let config = long("config")
+        .argument::<String>("FILE")
+        .complete_shell(ShellComp::File {
+            mask: Some("*.toml"),
+        });
+    let add = pure(())
+        .to_options()
+        .descr("Add a remote")
+        .command("add")
+        .group("Remotes");
+    let parser = construct!(verbose, target, config, add).to_options();
+
+    let r = parser.completions(&[], "--ver");
+    assert_eq!(r.len(), 1);
+    assert_eq!(r[0].text, "--verbose");
+    assert_eq!(r[0].display, "--verbose");
+    assert_eq!(
+        r[0].description.as_deref(),
+        Some("Be verbose, \\ and\ttabs")
+    );
+    assert_eq!(r[0].group, None);
+    assert_eq!(r[0].kind, CompletionKind::Flag);
+
+    let r = parser.completions(&[], "a");
+    assert_eq!(r.len(), 1);
+    assert_eq!(r[0].text, "add");
+    assert_eq!(r[0].description.as_deref(), Some("Add a remote"));
+    assert_eq!(r[0].group.as_deref(), Some("Remotes"));
+    assert_eq!(r[0].kind, CompletionKind::Command);
+
+    // values replace the whole word, metavariable is a placeholder
+    let r = parser.completions(&["-v"], "--target=");
+    let texts = r
+        .iter()
+        .map(|c| (c.text.as_str(), c.display.as_str(), c.kind.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        texts,
+        [
+            ("", "T", CompletionKind::Value),
+            ("--target=x86_64", "x86_64", CompletionKind::Value),
+            ("--target=arm", "arm", CompletionKind::Value),
+        ]
+    );
+    assert_eq!(r[1].description.as_deref(), Some("architecture"));
+
+    let r = parser.completions(&["--config"], "");
+    assert_eq!(
+        r[0].kind,
+        CompletionKind::FileHint {
+            dirs_only: false,
+            mask: Some("*.toml".to_owned())
+        }
+    );
+
+    assert_eq!(parser.completions(&[], "--nope"), Vec::new());
+}
+
+#[test]
+fn completion_env_protocol() {
+    let verbose = short('v').long("verbose").switch();
+    let parser = construct!(verbose)
+        .to_options()
+        .completion_env("BPAF_TEST_COMPLETE");
+
+    std::env::remove_var("BPAF_TEST_COMPLETE");
+    assert!(parser.run_inner(&["-v"]).unwrap());
+
+    // no arguments - completion script for the shell
+    std::env::set_var("BPAF_TEST_COMPLETE", "bash");
+    let r = parser
+        .run_inner(Args::from(&[]).set_name("app"))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(
+        r.contains("    line=\"BPAF_TEST_COMPLETE=bash $1\"\n"),
+        "{}",
+        r
+    );
+    assert!(r.ends_with("complete -o nosort -F _bpaf_dynamic_completion app\n"));
+
+    std::env::set_var("BPAF_TEST_COMPLETE", "nushell");
+    let r = parser
+        .run_inner(Args::from(&[]).set_name("app"))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(r.contains(
+        "with-env { BPAF_TEST_COMPLETE: nushell } { ^$spans.0 ...($spans | skip 1) } | from json"
+    ));
+
+    // words are passed verbatim, completion flags are not special
+    std::env::set_var("BPAF_TEST_COMPLETE", "bash");
+    let r = parser.run_inner(&["--ver"]).unwrap_err().unwrap_stdout();
+    assert_eq!(r, "COMPREPLY+=( '--verbose' )\n\n");
+    let r = parser
+        .run_inner(&["--bpaf-complete-rev=0", "--ver"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "COMPREPLY+=( '--verbose' )\n\n");
+
+    std::env::set_var("BPAF_TEST_COMPLETE", "cmd.exe");
+    assert!(parser.run_inner(&["-v"]).unwrap());
+    std::env::remove_var("BPAF_TEST_COMPLETE");
+}
+
+#[test]
+fn completion_with_context() {
+    fn branches(input: &String, ctx: &CompletionContext) -> Vec<(String, Option<String>)> {
+        let repo = ctx.value("--repo").or_else(|| ctx.value("-r"));
+        let names: &[&str] = match repo {
+            Some("bpaf") => &["master", "v0.9"],
+            Some(_) => &["main"],
+            None if ctx.contains("--all") => &["everything"],
+            None => &[],
+        };
+        names
+            .iter()
+            .filter(|name| name.starts_with(input.as_str()))
+            .map(|name| (name.to_string(), None))
+            .collect()
+    }
+
+    // branch is parsed before repo, but repo is still visible to the completer
+    let branch = long("branch")
+        .argument::<String>("BRANCH")
+        .complete_with_context(branches);
+    let repo = long("repo").short('r').argument::<String>("REPO");
+    let all = long("all").switch();
+    let parser = construct!(branch, repo, all).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["--repo", "bpaf", "--branch", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\tBRANCH\t\t\nmaster\tmaster\t\t\nv0.9\tv0.9\t\t\n\n");
+
+    let r = parser
+        .run_inner(Args::from(&["--repo=bpaf", "--branch", "m"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "master");
+
+    let r = parser
+        .run_inner(Args::from(&["-r", "bat", "--branch", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "main");
+
+    let r = parser
+        .run_inner(Args::from(&["--all", "--branch", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "everything");
+
+    // value for repo that is being typed is not visible yet
+    let r = parser
+        .run_inner(Args::from(&["--branch", "x", "--repo", "bpaf"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\tREPO\t\t\n\n");
+}
+
+#[test]
+fn type_driven_hints() {
+    #[derive(Debug, Clone)]
+    enum Mode {
+        Fast,
+        Slow,
+    }
+    impl std::str::FromStr for Mode {
+        type Err = String;
+        fn from_str(s: &str) -> Result<Self, String> {
+            match s {
+                "fast" => Ok(Mode::Fast),
+                "slow" => Ok(Mode::Slow),
+                _ => Err("expected fast or slow".to_owned()),
+            }
+        }
+    }
+    impl ValueHint for Mode {
+        const METAVAR: &'static str = "MODE";
+        const HINT: TypeHint = TypeHint::OneOf(&[("fast", Some("Go fast")), ("slow", None)]);
+    }
+
+    let output = long("output").argument::<std::path::PathBuf>("FILE");
+    let mode = long("mode").argument::<Mode>(Mode::METAVAR).value_hint();
+    let name = long("name").argument::<String>("NAME");
+    let log = long("log")
+        .argument::<std::path::PathBuf>("LOG")
+        .complete_shell(ShellComp::Dir { mask: Some("logs") });
+    let enabled = positional::<bool>("ENABLED");
+    let parser = construct!(output, mode, name, log, enabled).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["--output", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\nFile { mask: None }\n");
+
+    let r = parser
+        .run_inner(Args::from(&["--mode", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\tMODE\t\t\nfast\tfast\t\tGo fast\nslow\tslow\t\t\n\n");
+
+    let r = parser
+        .run_inner(Args::from(&["--mode=f"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--mode=fast");
+
+    // types without a hint still show the metavar
+    let r = parser
+        .run_inner(Args::from(&["--name", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\tNAME\t\t\n\n");
+
+    // explicit completion replaces the one from the type
+    let r = parser
+        .run_inner(Args::from(&["--log", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\nDir { mask: Some(\"logs\") }\n");
+
+    let r = parser
+        .run_inner(Args::from(&["t"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "true");
+
+    // derive takes both the metavar and the hint from the field type
+    #[derive(Debug, Clone, Bpaf)]
+    #[bpaf(options)]
+    #[allow(dead_code)]
+    struct Opts {
+        #[bpaf(value_hint)]
+        mode: Mode,
+    }
+
+    let r = opts()
+        .run_inner(Args::from(&["--mode", "s"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "slow");
+
+    let r = opts().run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    assert!(r.contains("--mode=MODE"), "{}", r);
+}
+
+#[test]
+fn type_hints_in_static_scripts() {
+    #[derive(Debug, Clone)]
+    struct Mode;
+    impl std::str::FromStr for Mode {
+        type Err = String;
+        fn from_str(_: &str) -> Result<Self, String> {
+            Ok(Mode)
+        }
+    }
+    impl ValueHint for Mode {
+        const HINT: TypeHint = TypeHint::OneOf(&[("fast", Some("Go fast")), ("slow", None)]);
+    }
+
+    let output = long("output").argument::<std::path::PathBuf>("FILE");
+    let mode = long("mode").argument::<Mode>("MODE").value_hint();
+    let rest = positional::<std::ffi::OsString>("REST");
+    let parser = construct!(output, mode, rest).to_options();
+
+    let r = parser.render_bash_completion("app");
+    assert!(
+        r.contains("'app --output') compopt -o filenames 2>/dev/null; _bpaf_app_add -f; return ;;")
+    );
+    assert!(r.contains("'app --mode') _bpaf_app_add -W 'fast slow'; return ;;"));
+    // OsString values are not necessarily file names
+    assert!(!r.contains("case \"$pos\""));
+
+    let r = parser.render_zsh_completion("app");
+    assert!(r.contains(
+        "'app --mode') [[ -n $eq ]] && compset -P 1 '*='; values=('fast:Go fast' 'slow'); _describe value values; return ;;"
+    ));
+
+    let r = parser.render_fish_completion("app");
+    assert!(r.contains(
+        "        case 'app --mode'\n            printf '%s\\t%s\\n' $pre'fast' 'Go fast'\n            echo $pre'slow'\n"
+    ));
+
+    let r = parser.render_fig_spec_with("app", |_| "gen".to_owned());
+    assert!(r.contains("suggestions: [\n"));
+    assert!(r.contains("{ name: \"fast\", description: \"Go fast\" },\n"));
+
+    let r = parser.render_carapace_spec_with("app", |_| "gen".to_owned());
+    assert!(r.contains("\"output\": [\"$files\"]\n"), "{}", r);
+    assert!(
+        r.contains("\"mode\": [\"fast\\tGo fast\", \"slow\"]\n"),
+        "{}",
+        r
+    );
+
+    let r = parser
+        .run_inner(Args::from(&[""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(!r.contains("File"), "{}", r);
+}
+
+#[test]
+fn completion_timeout_and_cache() {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::Duration;
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static SLOW: AtomicBool = AtomicBool::new(false);
+
+    fn images(input: &String) -> Vec<(&'static str, Option<&'static str>)> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        if SLOW.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_secs(5));
+        }
+        ["alpine", "archlinux", "debian"]
+            .iter()
+            .filter(|i| i.starts_with(input.as_str()))
+            .map(|i| (*i, Some("image")))
+            .collect()
+    }
+
+    let dir = std::env::temp_dir().join(format!("bpaf_comp_cache_{}", std::process::id()));
+    let cache = CompletionCache::in_dir(&dir, Duration::from_secs(3600));
+    let parser = long("image")
+        .argument::<String>("IMAGE")
+        .complete(images)
+        .timeout(Duration::from_millis(100))
+        .cache(cache.clone())
+        .to_options();
+    let complete = |word: &str| {
+        parser
+            .run_inner(Args::from(&["--image", word][..]).set_comp(0))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+    let expected = "\tIMAGE\t\t\nalpine\talpine\t\timage\narchlinux\tarchlinux\t\timage\n\n";
+
+    // first call fills the cache, second one uses it
+    assert_eq!(complete("a"), expected);
+    assert_eq!(complete("a"), expected);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+    // different partial input is a different key
+    assert_eq!(complete("d"), "debian");
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+    // slow completer with nothing cached offers nothing
+    SLOW.store(true, Ordering::SeqCst);
+    assert_eq!(complete("x"), "\tIMAGE\t\t\n\n");
+
+    // stale values are used when completer is too slow
+    let parser = long("image")
+        .argument::<String>("IMAGE")
+        .complete(images)
+        .timeout(Duration::from_millis(100))
+        .cache(CompletionCache::in_dir(&dir, Duration::from_secs(0)))
+        .to_options();
+    let r = parser
+        .run_inner(Args::from(&["--image", "a"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, expected);
+
+    // invalidated cache has nothing to fall back to, other files are kept
+    let other = dir.join("keep.txt");
+    std::fs::write(&other, "").unwrap();
+    cache.invalidate().unwrap();
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_file(&other).unwrap();
+    cache.invalidate().unwrap();
+    assert!(!dir.exists());
+    let r = parser
+        .run_inner(Args::from(&["--image", "a"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\tIMAGE\t\t\n\n");
+}
+
+#[test]
+#[should_panic(expected = "\"../app\" can't be used as a cache directory name")]
+fn completion_cache_rejects_paths() {
+    let _ = CompletionCache::new("../app", "images", std::time::Duration::from_secs(1));
+}
+
+#[test]
+#[should_panic(expected = "\"a/b\" can't be used as a cache directory name")]
+fn completion_cache_rejects_separators() {
+    let _ = CompletionCache::new("app", "a/b", std::time::Duration::from_secs(1));
+}
+
+#[test]
+fn completion_current_values() {
+    fn regions(input: &String) -> Vec<(&'static str, Option<&'static str>)> {
+        ["eu-west-1", "us-east-2"]
+            .iter()
+            .filter(|r| r.starts_with(input.as_str()))
+            .map(|r| (*r, None))
+            .collect()
+    }
+
+    let parser = long("region")
+        .env("BPAF_TEST_REGION")
+        .complete_env_value()
+        .argument::<String>("REGION")
+        .fallback("eu-west-1".to_owned())
+        .display_fallback()
+        .to_options();
+    let complete = |word: &str| {
+        parser
+            .run_inner(Args::from(&["--region", word][..]).set_comp(0))
+            .unwrap_err()
+            .unwrap_stdout()
+    };
+
+    // value from environment takes priority over the fallback
+    std::env::set_var("BPAF_TEST_REGION", "us-east-2");
+    assert_eq!(
+        complete(""),
+        "us-east-2\tus-east-2\t\tcurrent: from $BPAF_TEST_REGION\n\tREGION\t\t\n\n"
+    );
+    std::env::remove_var("BPAF_TEST_REGION");
+    assert_eq!(
+        complete(""),
+        "eu-west-1\teu-west-1\t\tcurrent: default\n\tREGION\t\t\n\n"
+    );
+
+    // current value is filtered by the typed prefix like everything else
+    assert_eq!(complete("us"), "\tREGION\t\t\n\n");
+
+    // and replaces the same value produced by a completion function
+    let parser = long("region")
+        .argument::<String>("REGION")
+        .complete(regions)
+        .fallback("eu-west-1".to_owned())
+        .display_fallback()
+        .to_options();
+    let r = parser
+        .run_inner(Args::from(&["--region", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "eu-west-1\teu-west-1\t\tcurrent: default\n\tREGION\t\t\nus-east-2\tus-east-2\t\t\n\n"
+    );
+
+    // environment values are not shown unless asked for, fallback is not current either
+    let parser = long("token")
+        .env("BPAF_TEST_TOKEN")
+        .argument::<String>("TOKEN")
+        .fallback("none".to_owned())
+        .display_fallback()
+        .to_options();
+    std::env::set_var("BPAF_TEST_TOKEN", "secret");
+    let r = parser
+        .run_inner(Args::from(&["--token", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    std::env::remove_var("BPAF_TEST_TOKEN");
+    assert_eq!(r, "\tTOKEN\t\t\n\n");
+}
@@ -123,3 +123,37 @@ Available commands:
     let r = parser.run_inner(&["one"]).unwrap();
     assert_eq!(r, One);
 }
+
+#[test]
+fn brief_and_detailed_doc_comments() {
+    #[derive(Debug, Clone, Bpaf)]
+    #[bpaf(options)]
+    #[allow(dead_code)]
+    struct Opts {
+        /// Produce more output
+        ///
+        /// Can be used multiple times
+        verbose: bool,
+    }
+
+    let r = opts().run_inner(&["-h"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [--verbose]
+
+Available options:
+        --verbose  Produce more output
+    -h, --help     Prints help information
+";
+    assert_eq!(r, expected);
+
+    let r = opts().run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [--verbose]
+
+Available options:
+        --verbose  Produce more output
+                   Can be used multiple times
+    -h, --help     Prints help information
+";
+    assert_eq!(r, expected);
+}
@@ -297,3 +297,60 @@ fn used_only_once_is_more_important_error() {
         "argument `--sort` cannot be used multiple times in this context"
     );
 }
+
+#[test]
+fn failure_kinds_and_exit_codes() {
+    let n = short('n')
+        .argument::<u32>("N")
+        .guard(|n| *n > 0, "must be positive");
+    let parser = n.to_options().version("1.0");
+
+    let r = parser.run_inner(&["-n", "x"]).unwrap_err();
+    assert_eq!(r.kind(), FailureKind::Validation);
+    assert_eq!(r.exit_code(), 1);
+
+    let r = parser.run_inner(&["-n", "0"]).unwrap_err();
+    assert_eq!(r.kind(), FailureKind::Validation);
+    assert_eq!(r.unwrap_stderr(), "`0`: must be positive");
+
+    let r = parser.run_inner(&["-n", "1", "-x"]).unwrap_err();
+    assert_eq!(r.kind(), FailureKind::Usage);
+    assert_eq!(r.exit_code(), 1);
+
+    let r = parser.run_inner(&[]).unwrap_err();
+    assert_eq!(r.kind(), FailureKind::Usage);
+
+    let r = parser.run_inner(&["--help"]).unwrap_err();
+    assert_eq!(r.kind(), FailureKind::Help);
+    assert_eq!(r.exit_code(), 0);
+
+    let r = parser.run_inner(&["--version"]).unwrap_err();
+    assert_eq!(r.kind(), FailureKind::Version);
+    assert_eq!(r.unwrap_stdout(), "Version: 1.0\n");
+
+    let r = parser
+        .run_inner(Args::from(&["-"]).set_comp(0))
+        .unwrap_err();
+    assert_eq!(r.kind(), FailureKind::Completion);
+
+    let parser = parser
+        .exit_code(FailureKind::Usage, 64)
+        .exit_code(FailureKind::Validation, 65)
+        .exit_code(FailureKind::Version, 3);
+    let r = parser.run_inner(&["-n", "x"]).unwrap_err();
+    assert!(matches!(r, ParseFailure::Stderr(..)));
+    assert_eq!(r.exit_code(), 65);
+    let r = parser.run_inner(&["-x"]).unwrap_err();
+    assert_eq!(r.exit_code(), 64);
+    let r = parser.run_inner(&["--version"]).unwrap_err();
+    assert!(matches!(r, ParseFailure::Stdout(..)));
+    assert_eq!(
+        r.status(),
+        ExitStatus {
+            kind: FailureKind::Version,
+            code: 3
+        }
+    );
+    let r = parser.run_inner(&["--help"]).unwrap_err();
+    assert_eq!(r.exit_code(), 0);
+}
@@ -0,0 +1,154 @@
+#![cfg(unix)]
+use bpaf::{parsers::ExternalCommand, *};
+use std::{ffi::OsString, ops::Deref, os::unix::fs::PermissionsExt, path::PathBuf};
+
+/// Directory with fake plugins, removed when dropped
+struct PluginDir(PathBuf);
+
+impl Deref for PluginDir {
+    type Target = PathBuf;
+    fn deref(&self) -> &PathBuf {
+        &self.0
+    }
+}
+
+impl Drop for PluginDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn plugin_dir(name: &str) -> PluginDir {
+    let dir = std::env::temp_dir().join(format!("bpaf-external-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    for (file, mode) in [
+        ("mytool-build-docs", 0o755),
+        ("mytool-foo", 0o755),
+        ("mytool-nope", 0o644),
+        ("othertool-bar", 0o755),
+    ] {
+        let path = dir.join(file);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+    PluginDir(dir)
+}
+
+#[derive(Debug, Clone)]
+enum Cmd {
+    Build(bool),
+    External(ExternalCommand),
+}
+
+fn parser(dir: &PathBuf) -> OptionParser<Cmd> {
+    let build = short('r')
+        .long("release")
+        .switch()
+        .map(Cmd::Build)
+        .to_options()
+        .descr("Build the project")
+        .command("build");
+    let external = external_commands("mytool-")
+        .search_in([dir])
+        .map(Cmd::External);
+    construct!([build, external]).to_options()
+}
+
+#[test]
+fn dispatch_to_external() {
+    let dir = plugin_dir("dispatch");
+    let parser = parser(&dir);
+
+    let r = parser
+        .run_inner(&["foo", "-vx", "--name=bar", "baz", "--", "-q"])
+        .unwrap();
+    let ext = match r {
+        Cmd::External(ext) => ext,
+        Cmd::Build(_) => panic!("expected external command"),
+    };
+    assert_eq!(ext.name, "foo");
+    assert_eq!(ext.path, dir.join("mytool-foo"));
+    let expected = ["-vx", "--name=bar", "baz", "--", "-q"]
+        .iter()
+        .map(OsString::from)
+        .collect::<Vec<_>>();
+    assert_eq!(ext.args, expected);
+
+    let r = parser.run_inner(&["build", "--release"]).unwrap();
+    assert!(matches!(r, Cmd::Build(true)));
+}
+
+#[test]
+fn external_in_help() {
+    let dir = plugin_dir("help");
+    let r = parser(&dir)
+        .run_inner(&["--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+
+    let expected = "\
+Usage: COMMAND ...
+
+Available options:
+    -h, --help  Prints help information
+
+Available commands:
+    build       Build the project
+    build-docs
+    foo
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn external_suggestions() {
+    let dir = plugin_dir("suggest");
+    let r = parser(&dir)
+        .run_inner(&["fooo"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such command or positional: `fooo`, did you mean `foo`?"
+    );
+
+    let r = parser(&dir)
+        .run_inner(&["nope"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `COMMAND ...`, got `nope`. Pass `--help` for usage information"
+    );
+
+    let r = external_commands("mytool-")
+        .search_in([&*dir])
+        .to_options()
+        .run_inner(&[])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `COMMAND ...`, pass `--help` for usage information"
+    );
+}
+
+#[test]
+fn external_completion() {
+    let dir = plugin_dir("complete");
+    let r = parser(&dir)
+        .run_inner(Args::from(&["b"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "build\tbuild\t\tBuild the project\nbuild-docs\tbuild-docs\t\t\n\n"
+    );
+
+    let r = parser(&dir)
+        .run_inner(Args::from(&["f"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "foo");
+}
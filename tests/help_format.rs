@@ -234,6 +234,24 @@ fn enum_with_docs() {
         Att,
     }
 
+    let r = mode()
+        .to_options()
+        .run_inner(&["-h"])
+        .unwrap_err()
+        .unwrap_stdout();
+
+    let expected = "\
+Usage: (--intel | --att)
+
+Pick mode:
+        --intel  help
+        --att    help
+
+Available options:
+    -h, --help   Prints help information
+";
+    assert_eq!(r, expected);
+
     let r = mode()
         .to_options()
         .run_inner(&["--help"])
@@ -245,7 +263,9 @@ Usage: (--intel | --att)
 
 Pick mode:
         --intel  help
+                 absent
         --att    help
+                 Hidden
 
 Available options:
     -h, --help   Prints help information
@@ -634,3 +654,292 @@ fn help_and_version_newline() {
         .unwrap_stdout();
     assert_eq!(r, "Version: 1\n");
 }
+
+#[test]
+fn command_groups() {
+    let commit = pure(())
+        .to_options()
+        .descr("Record changes to the repository")
+        .command("commit")
+        .group("Porcelain commands");
+    let cat_file = pure(())
+        .to_options()
+        .descr("Provide content of repository objects")
+        .command("cat-file")
+        .group("Plumbing commands");
+    let status = pure(())
+        .to_options()
+        .descr("Show the working tree status")
+        .command("status")
+        .group("Porcelain commands");
+    let help = pure(()).to_options().descr("Show help").command("help");
+    let parser = construct!([commit, cat_file, status, help]).to_options();
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: COMMAND ...
+
+Available options:
+    -h, --help  Prints help information
+
+Available commands:
+    help        Show help
+
+Porcelain commands:
+    commit      Record changes to the repository
+    status      Show the working tree status
+
+Plumbing commands:
+    cat-file    Provide content of repository objects
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn help_subcommand() {
+    let add = short('f')
+        .help("Fetch after adding")
+        .switch()
+        .to_options()
+        .descr("Add a remote")
+        .command("add");
+    let remote = add.to_options().descr("Manage remotes").command("remote");
+    let build = pure(false)
+        .to_options()
+        .descr("Build the project")
+        .command("build")
+        .short('b');
+    let parser = construct!([remote, build]).to_options().help_command();
+
+    let r = parser.run_inner(&["help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: COMMAND ...
+
+Available options:
+    -h, --help  Prints help information
+
+Available commands:
+    remote      Manage remotes
+    build, b    Build the project
+    help        Prints help for a command
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["help", "remote", "add"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Add a remote
+
+Usage: remote add [-f]
+
+Available options:
+    -f          Fetch after adding
+    -h, --help  Prints help information
+";
+    assert_eq!(r, expected);
+    let r2 = parser
+        .run_inner(&["remote", "add", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, r2);
+
+    let r = parser
+        .run_inner(&["help", "b"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Build the project
+
+Usage: build \n
+Available options:
+    -h, --help  Prints help information
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["help", "biuld"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such command or positional: `biuld`, did you mean `build`?"
+    );
+
+    let r = parser
+        .run_inner(&["help", "remote", "ad"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such command or positional: `ad`, did you mean `add`?"
+    );
+}
+
+#[test]
+fn searchable_help() {
+    let verbose = short('v')
+        .long("verbose")
+        .help("Produce more output")
+        .switch();
+    let color = long("color").help("Use colors").switch();
+    let width = long("width").help("Output width").argument::<usize>("COLS");
+    let display = construct!(color, width).group_help("Display options");
+    let name = long("name").help("Remote name").argument::<String>("NAME");
+    let add = name.to_options().descr("Add a remote").command("add");
+    let remote = add.to_options().descr("Manage remotes").command("remote");
+    let parser = construct!(verbose, display, remote)
+        .to_options()
+        .descr("A tool")
+        .searchable_help();
+
+    let r = parser
+        .run_inner(&["--help", "width"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: [-v] [--color] --width=COLS COMMAND ...
+
+Display options
+        --width=COLS  Output width
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["--help=display"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: [-v] [--color] --width=COLS COMMAND ...
+
+Display options
+        --color       Use colors
+        --width=COLS  Output width
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["-h", "name"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: [-v] [--color] --width=COLS COMMAND ...
+
+Found in `remote add` command:
+        --name=NAME  Remote name
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["remote", "--help", "add"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: remote COMMAND ...
+
+Available commands:
+    add  Add a remote
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["--help", "banana"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: [-v] [--color] --width=COLS COMMAND ...
+
+Nothing matches `banana`
+";
+    assert_eq!(r, expected);
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    assert!(r.starts_with("A tool\n"));
+}
+
+#[test]
+fn brief_and_detailed_help() {
+    let verbose = short('v')
+        .long("verbose")
+        .help("Produce more output")
+        .long_help("Can be used multiple times")
+        .switch();
+    let jobs = long("jobs")
+        .long_help("Number of jobs\n\nDefaults to the number of CPUs")
+        .argument::<usize>("N");
+    let file = positional::<String>("FILE")
+        .help("File to process")
+        .long_help("Use - for stdin");
+    let check = pure(())
+        .to_options()
+        .command("check")
+        .help("Check the project")
+        .long_help("Runs all the lints");
+    let parser = construct!(verbose, jobs, file, check).to_options();
+
+    let r = parser.run_inner(&["-h"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-v] --jobs=N FILE COMMAND ...
+
+Available positional items:
+    FILE           File to process
+
+Available options:
+    -v, --verbose  Produce more output
+        --jobs=N   Number of jobs
+    -h, --help     Prints help information
+
+Available commands:
+    check          Check the project
+";
+    assert_eq!(r, expected);
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-v] --jobs=N FILE COMMAND ...
+
+Available positional items:
+    FILE           File to process
+                   Use - for stdin
+
+Available options:
+    -v, --verbose  Produce more output
+                   Can be used multiple times
+        --jobs=N   Number of jobs
+                   Defaults to the number of CPUs
+    -h, --help     Prints help information
+
+Available commands:
+    check          Check the project
+                   Runs all the lints
+";
+    assert_eq!(r, expected);
+
+    let r2 = parser.run_inner(&["-h", "-h"]).unwrap_err().unwrap_stdout();
+    assert_eq!(r, r2);
+}
+
+#[test]
+fn examples_in_help() {
+    let verbose = short('v').help("Verbose output").switch();
+    let parser = verbose
+        .to_options()
+        .example("app -v", "Run with verbose output")
+        .footer("See the docs for more");
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-v]
+
+Available options:
+    -v          Verbose output
+    -h, --help  Prints help information
+
+Examples:
+    app -v      Run with verbose output
+
+See the docs for more
+";
+    assert_eq!(r, expected);
+}
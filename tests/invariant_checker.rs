@@ -58,3 +58,67 @@ fn fixed_adjacent_is_not_ok() {
     let c = short('c').switch();
     construct!(ab, c).to_options().check_invariants(false);
 }
+
+#[test]
+fn examples_parse() {
+    let verbose = short('v').switch();
+    let remote = positional::<String>("NAME")
+        .to_options()
+        .example("app add origin", "Add a remote")
+        .command("add");
+    construct!(verbose, remote)
+        .to_options()
+        .example("app -v add origin", "Add a remote verbosely")
+        .example("app --help", "Print help")
+        .check_invariants(false)
+}
+
+#[should_panic(
+    expected = "Example `app add --force origin` fails to parse: `--force` is not expected"
+)]
+#[test]
+fn stale_example_nested() {
+    let verbose = short('v').switch();
+    let remote = positional::<String>("NAME")
+        .to_options()
+        .example("app add --force origin", "Add a remote forcefully")
+        .command("add");
+    construct!(verbose, remote)
+        .to_options()
+        .check_invariants(false)
+}
+
+#[test]
+fn examples_with_quotes() {
+    let message = long("message")
+        .argument::<String>("MSG")
+        .guard(|m| m == "fix typo", "unexpected message");
+    let path = positional::<String>("PATH").guard(|p| p == "my file", "unexpected path");
+    construct!(message, path)
+        .to_options()
+        .example(r#"app --message "fix typo" my\ file"#, "Double quotes")
+        .example("app --message='fix typo' 'my file'", "Single quotes")
+        .check_invariants(false)
+}
+
+#[should_panic(
+    expected = "Example `app 'origin` is not a valid command line: unterminated single quote"
+)]
+#[test]
+fn example_with_broken_quotes() {
+    positional::<String>("NAME")
+        .to_options()
+        .example("app 'origin", "Broken example")
+        .check_invariants(false)
+}
+
+#[test]
+fn examples_keep_color_choice() {
+    use bpaf::{batteries::color_choice, doc::ColorChoice};
+    ColorChoice::Auto.set_global();
+    color_choice()
+        .to_options()
+        .example("app --color=never", "Disable colors")
+        .check_invariants(false);
+    assert_eq!(ColorChoice::global(), ColorChoice::Auto);
+}
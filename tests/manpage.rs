@@ -128,3 +128,233 @@ fn very_nested() {
     #[cfg(unix)]
     assert!(write_updated(&roff, "tests/very_nested.1").unwrap());
 }
+
+#[test]
+fn multi_page() {
+    let add = positional::<String>("NAME")
+        .help("Name of the remote")
+        .to_options()
+        .descr("Add a remote")
+        .command("add");
+    let remote = construct!([add])
+        .to_options()
+        .descr("Manage remotes")
+        .header("Remotes are other copies of the repository.")
+        .command("remote");
+    let build = short('r')
+        .long("release")
+        .help("Build in release mode")
+        .switch()
+        .map(|r| r.to_string())
+        .to_options()
+        .descr("Build the project")
+        .command("build");
+    let pages = construct!([build, remote])
+        .to_options()
+        .descr("Version control")
+        .render_manpages("tool", Section::General, Some("Aug 2022"), None, None);
+
+    let names = pages.iter().map(|p| p.0.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "tool.1",
+            "tool-build.1",
+            "tool-remote.1",
+            "tool-remote-add.1"
+        ]
+    );
+
+    let expected = r#".ie \n(.g .ds Aq \(aq
+.el .ds Aq '
+.TH tool-remote 1 Aug\ 2022 - ""
+.SH NAME
+\fRtool\-remote \- \fP\fRManage remotes\fP
+.SH SYNOPSIS
+\fBtool\fP\fR \fP\fBremote\fP\fR \fP\fICOMMAND ...\fP
+.SH DESCRIPTION
+.PP
+\fRRemotes are other copies of the repository.\fP
+.SH OPTIONS
+.PP
+.SS AVAILABLE\ OPTIONS:
+.TP
+\fB\-h\fP\fR, \fP\fB\-\-help\fP
+\fRPrints help information\fP
+.PP
+.PP
+.SS AVAILABLE\ COMMANDS:
+.TP
+\fBadd\fP
+\fRAdd a remote\fP
+.PP
+.SH SEE\ ALSO
+\fBtool\fP\fR(1), \fP\fBtool\-remote\-add\fP\fR(1)\fP"#;
+    assert_eq!(pages[2].1, expected);
+}
+
+#[test]
+fn trailing_sections() {
+    let user = long("user")
+        .env("APP_USER")
+        .help("Log in as this user")
+        .argument::<String>("USER");
+    let roff = user
+        .to_options()
+        .descr("Log in")
+        .exit_status(0, "Success")
+        .exit_status(64, "Invalid command line")
+        .file("~/.app.toml", "User configuration")
+        .example("app --user alice", "Log in as alice")
+        .authors("Alice <alice@example.com>")
+        .bug_url("https://example.com/issues")
+        .render_manpage("app", Section::General, None, None, None);
+
+    let expected = r#".ie \n(.g .ds Aq \(aq
+.el .ds Aq '
+.TH app 1 - - ""
+.SH NAME
+\fRapp \- \fP\fRLog in\fP
+.SH SYNOPSIS
+\fBapp\fP\fR \fP\fB\-\-user\fP\fR=\fP\fIUSER\fP
+.PP
+.SS AVAILABLE\ OPTIONS:
+.TP
+\fB    \-\-user\fP\fR=\fP\fIUSER\fP
+\fRLog in as this user\fP
+.PP
+.TP
+\fRUses environment variable \fP\fBAPP_USER\fP
+.PP
+.TP
+\fB\-h\fP\fR, \fP\fB\-\-help\fP
+\fRPrints help information\fP
+.PP
+.SH EXIT\ STATUS
+.TP
+\fB0\fP
+\fRSuccess\fP
+.PP
+.TP
+\fB64\fP
+\fRInvalid command line\fP
+.PP
+.SH ENVIRONMENT
+.TP
+\fBAPP_USER\fP
+\fRLog in as this user\fP
+.PP
+.SH FILES
+.TP
+\fB~/.app.toml\fP
+\fRUser configuration\fP
+.PP
+.SH EXAMPLES
+.TP
+\fBapp \-\-user alice\fP
+\fRLog in as alice\fP
+.PP
+.SH AUTHORS
+\fRAlice <alice@example.com>\fP
+.SH REPORTING\ BUGS
+\fRReport bugs at \fP\fBhttps://example.com/issues\fP"#;
+    assert_eq!(roff, expected);
+}
+
+#[test]
+fn environment_lists_every_variable() {
+    let user = long("user")
+        .env("APP_USER")
+        .env("USER")
+        .help("Log in as this user")
+        .argument::<String>("USER");
+    let roff = user
+        .to_options()
+        .render_manpage("app", Section::General, None, None, None);
+
+    let expected = r#".SH ENVIRONMENT
+.TP
+\fBAPP_USER\fP
+\fRLog in as this user\fP
+.PP
+.TP
+\fBUSER\fP
+\fRLog in as this user\fP
+.PP
+"#;
+    assert!(roff.ends_with(expected), "{}", roff);
+}
+
+#[test]
+fn command_groups() {
+    let commit = pure(())
+        .to_options()
+        .descr("Record changes")
+        .command("commit")
+        .group("Porcelain commands");
+    let check = pure(()).to_options().descr("Check things").command("check");
+    let r = construct!([commit, check]).to_options().render_manpage(
+        "git",
+        Section::General,
+        None,
+        None,
+        None,
+    );
+
+    let expected = r#".ie \n(.g .ds Aq \(aq
+.el .ds Aq '
+.TH git 1 - - ""
+.PP
+.SH SYNOPSIS
+.nf
+\fBgit\fP\fR \fP\fICOMMAND ...\fP\fR
+\fP\fBgit\fP\fR \fP\fBcommit\fP\fR \fP\fR
+\fP\fBgit\fP\fR \fP\fBcheck\fP\fR \fP\fR
+\fP
+.fi
+.SH GIT\ 
+.SH SYNOPSIS
+\fBgit\fP\fR \fP\fICOMMAND ...\fP
+.PP
+.SS AVAILABLE\ OPTIONS:
+.TP
+\fB\-h\fP\fR, \fP\fB\-\-help\fP
+\fRPrints help information\fP
+.PP
+.PP
+.SS AVAILABLE\ COMMANDS:
+.TP
+\fBcheck\fP
+\fRCheck things\fP
+.PP
+.PP
+.SS PORCELAIN\ COMMANDS:
+.TP
+\fBcommit\fP
+\fRRecord changes\fP
+.PP
+.SH GIT\ COMMIT\ 
+.SH NAME
+\fRgit \- \fP\fRRecord changes\fP
+.SH SYNOPSIS
+\fBgit\fP\fR \fP\fBcommit\fP\fR \fP
+.PP
+.SS AVAILABLE\ OPTIONS:
+.TP
+\fB\-h\fP\fR, \fP\fB\-\-help\fP
+\fRPrints help information\fP
+.PP
+.SH GIT\ CHECK\ 
+.SH NAME
+\fRgit \- \fP\fRCheck things\fP
+.SH SYNOPSIS
+\fBgit\fP\fR \fP\fBcheck\fP\fR \fP
+.PP
+.SS AVAILABLE\ OPTIONS:
+.TP
+\fB\-h\fP\fR, \fP\fB\-\-help\fP
+\fRPrints help information\fP
+.PP
+"#;
+    assert_eq!(r, expected);
+}
@@ -254,3 +254,326 @@ Available options:
 
     assert_eq!(r, expected);
 }
+
+#[test]
+fn command_groups() {
+    let commit = pure(())
+        .to_options()
+        .descr("Record changes")
+        .command("commit")
+        .group("Porcelain commands");
+    let check = pure(()).to_options().descr("Check things").command("check");
+    let r = construct!([commit, check])
+        .to_options()
+        .render_markdown("git");
+
+    let expected = "
+
+
+# Command summary
+
+  * [`git`↴](#git)
+  * [`git commit`↴](#git-commit)
+  * [`git check`↴](#git-check)
+
+## git
+
+**Usage**: **`git`** _`COMMAND ...`_
+
+**Available options:**
+- **`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+
+**Available commands:**
+- **`check`** &mdash; \n  Check things
+
+
+
+**Porcelain commands:**
+- **`commit`** &mdash; \n  Record changes
+
+
+## git commit
+
+Record changes
+
+**Usage**: **`git`** **`commit`** \n
+**Available options:**
+- **`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+## git check
+
+Check things
+
+**Usage**: **`git`** **`check`** \n
+**Available options:**
+- **`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn examples() {
+    let r = short('v')
+        .help("Verbose output")
+        .switch()
+        .to_options()
+        .example("app -v", "Run with verbose output")
+        .render_markdown("app");
+
+    let expected = "# app
+
+**Usage**: **`app`** \\[**`-v`**\\]
+
+**Available options:**
+- **`-v`** &mdash; \n  Verbose output
+- **`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+
+**Examples:**
+- **`app -v`** &mdash; \n  Run with verbose output
+
+
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn mdbook_pages() {
+    let name = positional::<String>("NAME").help("Name of the remote");
+    let add = construct!(name)
+        .to_options()
+        .descr("Add a remote")
+        .command("add");
+    let verbose = short('v').long("verbose").help("Verbose output").switch();
+    let remote = construct!(verbose, add)
+        .to_options()
+        .descr("Manage remotes")
+        .command("remote");
+    let pages = construct!([remote]).to_options().render_mdbook("app");
+
+    let names = pages.iter().map(|p| p.0.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["SUMMARY.md", "app.md", "app-remote.md", "app-remote-add.md"]
+    );
+
+    let expected = "# Summary
+
+- [app](app.md)
+    - [app remote](app-remote.md)
+        - [app remote add](app-remote-add.md)
+";
+    assert_eq!(pages[0].1, expected);
+
+    let expected = "# app remote
+
+Manage remotes
+
+**Usage**: **`app`** **`remote`** \\[**`-v`**\\] _`COMMAND ...`_
+
+**Available options:**
+- <a id=\"option-verbose\"></a>**`-v`**, **`--verbose`** &mdash; \n  Verbose output
+- <a id=\"option-help\"></a>**`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+
+**Available commands:**
+- <a id=\"command-add\"></a>[**`add`**](app-remote-add.md) &mdash; \n  Add a remote
+
+
+";
+    assert_eq!(pages[2].1, expected);
+
+    let expected = "# app remote add
+
+Add a remote
+
+**Usage**: **`app`** **`remote`** **`add`** _`NAME`_
+
+**Available positional items:**
+- <a id=\"positional-name\"></a>_`NAME`_ &mdash; \n  Name of the remote
+
+
+
+**Available options:**
+- <a id=\"option-help\"></a>**`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+";
+    assert_eq!(pages[3].1, expected);
+}
+
+#[test]
+fn mdbook_env_and_long_only_names() {
+    let user = long("user")
+        .env("APP_USER")
+        .help("User name")
+        .argument::<String>("USER");
+    let parser = user.to_options();
+    let pages = parser.render_mdbook("app");
+    let expected = "# app
+
+**Usage**: **`app`** **`--user`**=_`USER`_
+
+**Available options:**
+- <a id=\"option-user\"></a>**`    --user`**=_`USER`_ &mdash; \n  User name\n   \n  Uses environment variable **`APP_USER`**
+- <a id=\"option-help\"></a>**`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+";
+    assert_eq!(pages[1].1, expected);
+    assert!(parser
+        .render_markdown("app")
+        .contains("Uses environment variable **`APP_USER`**"));
+}
+
+fn remote_parser() -> OptionParser<(bool, String, String)> {
+    let name = positional::<String>("NAME").help("Name of the remote\n\nMore details\n here");
+    let add = construct!(name)
+        .to_options()
+        .descr("Add a remote")
+        .command("add");
+    let verbose = short('v').long("verbose").help("Verbose output").switch();
+    let user = long("user")
+        .env("USER")
+        .help("User name")
+        .argument::<String>("USER");
+    construct!(verbose, user, add)
+        .to_options()
+        .descr("Manage remotes")
+        .footer("Footer")
+}
+
+#[test]
+fn asciidoc() {
+    let r = remote_parser().render_asciidoc("app");
+    let expected = "= app
+
+Manage remotes
+
+**Usage**: **`+app+`** [**`+-v+`**] **`+--user+`**=__`+USER+`__ __`+COMMAND ...+`__
+
+**Available options:**
+
+**`+-v+`**, **`+--verbose+`**:: Verbose output
+
+**`+--user+`**=__`+USER+`__:: User name
++
+Uses environment variable **`+USER+`**
+
+**`+-h+`**, **`+--help+`**:: Prints help information
+
+**Available commands:**
+
+**`+add+`**:: Add a remote
+
+Footer
+
+== app add
+
+Add a remote
+
+**Usage**: **`+app+`** **`+add+`** __`+NAME+`__
+
+**Available positional items:**
+
+__`+NAME+`__:: Name of the remote
++
+More details +
+here
+
+**Available options:**
+
+**`+-h+`**, **`+--help+`**:: Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn rst() {
+    let r = remote_parser().render_rst("app");
+    let expected = "app
+===
+
+Manage remotes
+
+**Usage**: ``app`` [``-v``] ``--user``\\ =\\ *USER* *COMMAND ...*
+
+**Available options:**
+
+``-v``, ``--verbose``
+    Verbose output
+
+``--user``\\ =\\ *USER*
+    User name
+
+    Uses environment variable ``USER``
+
+``-h``, ``--help``
+    Prints help information
+
+**Available commands:**
+
+``add``
+    Add a remote
+
+Footer
+
+app add
+-------
+
+Add a remote
+
+**Usage**: ``app`` ``add`` *NAME*
+
+**Available positional items:**
+
+*NAME*
+    Name of the remote
+
+    More details
+    here
+
+**Available options:**
+
+``-h``, ``--help``
+    Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn rst_escapes_text() {
+    let doc = Doc::from("Use *stars* and `ticks`");
+    assert_eq!(doc.render_rst(true), "Use \\*stars\\* and \\`ticks\\`\n");
+}
+
+#[test]
+fn asciidoc_escapes_text() {
+    let doc = Doc::from("Use *stars*, _under_ `ticks`, #hash and {attr}");
+    assert_eq!(
+        doc.render_asciidoc(true),
+        "Use \\*stars\\*, \\_under\\_ \\`ticks\\`, \\#hash and \\{attr}\n"
+    );
+}
+
+#[test]
+fn asciidoc_plus_in_names() {
+    let cpp = long("c++").help("Enable C++").switch();
+    let std = long("std")
+        .help("Language standard")
+        .argument::<String>("[C++]");
+    let r = construct!(cpp, std).to_options().render_asciidoc("app");
+    assert!(r.contains("**`pass:c[--c++]`**:: Enable C++\n"), "{}", r);
+    assert!(
+        r.contains("**`+--std+`**=__`pass:c[<[C++\\]>]`__:: Language standard\n"),
+        "{}",
+        r
+    );
+}